@@ -1,18 +1,37 @@
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::Instant,
+};
 
-use serenity::model::id::GuildId;
+use serenity::model::id::{GuildId, UserId};
 
-use crate::cache::GuildCacheEntry;
+use crate::{
+    cache::{ConfigHistoryEntry, GuildCacheEntry, PaceHistoryEntry, PendingImport, RunCacheEntry},
+    ws::EventId,
+};
 
 pub type CacheKey = GuildId;
+pub type ProcessedEventKey = (String, EventId, i64);
 pub struct Cache {
     pub entries: HashMap<CacheKey, GuildCacheEntry>,
+    pub pace_history: HashMap<String, PaceHistoryEntry>,
+    pub runs: HashMap<String, RunCacheEntry>,
+    pub processed_events: HashMap<ProcessedEventKey, Instant>,
+    pub pending_imports: HashMap<(GuildId, UserId), PendingImport>,
+    pub pending_role_message_refreshes: HashSet<GuildId>,
+    pub config_history: HashMap<GuildId, VecDeque<ConfigHistoryEntry>>,
 }
 
 impl Cache {
     pub fn new() -> Self {
         Self {
             entries: HashMap::new(),
+            pace_history: HashMap::new(),
+            runs: HashMap::new(),
+            processed_events: HashMap::new(),
+            pending_imports: HashMap::new(),
+            pending_role_message_refreshes: HashSet::new(),
+            config_history: HashMap::new(),
         }
     }
 }
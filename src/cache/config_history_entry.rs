@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+use serenity::model::{id::UserId, Timestamp};
+
+use crate::cache::ConfigSnapshot;
+
+// A configuration change that can be reverted with /config_revert.
+#[derive(Serialize, Deserialize)]
+pub struct ConfigHistoryEntry {
+    pub id: u32,
+    pub user_id: UserId,
+    pub action: String,
+    pub before: ConfigSnapshot,
+    pub after: ConfigSnapshot,
+    pub created_at: Timestamp,
+}
+
+impl ConfigHistoryEntry {
+    // Counts of removed and added lines, e.g. `whitelist -1 +0, pace-roles -5 +0`.
+    pub fn summary(&self) -> String {
+        [
+            ("whitelist", &self.before.whitelist, &self.after.whitelist),
+            ("pace-roles", &self.before.roles, &self.after.roles),
+            ("settings", &self.before.settings, &self.after.settings),
+        ]
+        .iter()
+        .filter_map(|(name, before, after)| {
+            let diff = ConfigSnapshot::diff_lines(before, after);
+            if diff.is_empty() {
+                return None;
+            }
+            let removed = diff.iter().filter(|line| line.starts_with('-')).count();
+            Some(format!("{} -{} +{}", name, removed, diff.len() - removed))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+// The parts of a server's configuration that commands change.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    pub whitelist: Vec<String>,
+    pub roles: Vec<String>,
+    pub settings: Vec<String>,
+}
+
+impl ConfigSnapshot {
+    // Lines only in `before` prefixed with `-` and lines only in `after` with `+`.
+    pub fn diff_lines(before: &[String], after: &[String]) -> Vec<String> {
+        let mut diff = before
+            .iter()
+            .filter(|line| !after.contains(line))
+            .map(|line| format!("- {}", line))
+            .collect::<Vec<_>>();
+        diff.extend(
+            after
+                .iter()
+                .filter(|line| !before.contains(line))
+                .map(|line| format!("+ {}", line)),
+        );
+        diff
+    }
+}
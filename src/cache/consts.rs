@@ -1,6 +1,23 @@
-pub const BASTION_EMOJI: &'static str = "<:bastion:1306296287729291334>";
-pub const FORT_EMOJI: &'static str = "<:fortress:1306295901278437397>";
-pub const PORTAL_EMOJI: &'static str = "<:portal:1306295821246922813>";
-pub const SH_EMOJI: &'static str = "<:sh:1306295768109420605>";
-pub const END_EMOJI: &'static str = "<:end:1306295918638796941>";
+pub const BASTION_EMOJI: &str = "<:bastion:1306296287729291334>";
+pub const FORT_EMOJI: &str = "<:fortress:1306295901278437397>";
+pub const PORTAL_EMOJI: &str = "<:portal:1306295821246922813>";
+pub const SH_EMOJI: &str = "<:sh:1306295768109420605>";
+pub const END_EMOJI: &str = "<:end:1306295918638796941>";
 pub const EDIT_MESSAGE_DELAY: u64 = 5;
+pub const MESSAGE_DELETE_DELAY: u64 = 30;
+pub const PACE_HISTORY_SIZE: usize = 10;
+pub const PACE_HISTORY_MIN_SAMPLES: usize = 3;
+// Runners with a pace history, the one that finished least recently is dropped
+// to make room for a new one.
+pub const PACE_HISTORY_MAX_RUNNERS: usize = 1000;
+pub const RUN_CACHE_TTL: u64 = 3600;
+pub const PROCESSED_EVENT_TTL: u64 = 3600;
+// Interaction tokens expire after 15 minutes, so the confirm button does too.
+pub const PENDING_IMPORT_TTL: u64 = 900;
+pub const DM_FAILURE_LIMIT: u8 = 3;
+// Pace-roles are usually created or deleted in bulk, so role selection messages
+// are refreshed once this many seconds after the last change.
+pub const ROLE_MESSAGE_REFRESH_DELAY: u64 = 10;
+// Configuration changes kept per server for /config_revert, which also fits in
+// the 25 autocomplete choices.
+pub const CONFIG_HISTORY_SIZE: usize = 25;
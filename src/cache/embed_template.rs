@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use crate::{
+    cache::{BASTION_EMOJI, END_EMOJI, FORT_EMOJI, PORTAL_EMOJI, SH_EMOJI},
+    dispatcher::{
+        CREDITS_EMOJI, KICK_EMOJI, OFFLINE_EMOJI, PEARL_EMOJI, ROD_EMOJI, TWITCH_EMOJI,
+        YOUTUBE_EMOJI,
+    },
+};
+
+pub const EMBED_FIELDS: [&str; 7] = [
+    "live",
+    "splits",
+    "time",
+    "items",
+    "bastionless",
+    "projected",
+    "warning",
+];
+
+pub const EMBED_EMOJIS: [(&str, &str); 12] = [
+    ("bastion", BASTION_EMOJI),
+    ("fortress", FORT_EMOJI),
+    ("portal", PORTAL_EMOJI),
+    ("stronghold", SH_EMOJI),
+    ("end", END_EMOJI),
+    ("credits", CREDITS_EMOJI),
+    ("pearl", PEARL_EMOJI),
+    ("rod", ROD_EMOJI),
+    ("twitch", TWITCH_EMOJI),
+    ("youtube", YOUTUBE_EMOJI),
+    ("kick", KICK_EMOJI),
+    ("offline", OFFLINE_EMOJI),
+];
+
+#[derive(Clone)]
+pub struct EmbedTemplate {
+    pub fields: Vec<String>,
+    pub compact: bool,
+    // Colours keyed by `Split::to_str`, and "F" for finishes.
+    pub split_colors: HashMap<String, u32>,
+    // Colour pace embeds by how far under the guild's thresholds the split is.
+    pub pace_colors: bool,
+    // Thumbnail urls keyed like `split_colors`.
+    pub split_thumbnails: HashMap<String, String>,
+    // Custom emojis keyed by the names in `EMBED_EMOJIS`.
+    pub emojis: HashMap<String, String>,
+}
+
+impl Default for EmbedTemplate {
+    fn default() -> Self {
+        Self {
+            fields: EMBED_FIELDS.iter().map(|field| field.to_string()).collect(),
+            compact: false,
+            split_colors: HashMap::new(),
+            pace_colors: true,
+            split_thumbnails: HashMap::new(),
+            emojis: HashMap::new(),
+        }
+    }
+}
+
+impl EmbedTemplate {
+    pub fn has_field(&self, field: &str) -> bool {
+        self.fields.iter().any(|f| f == field)
+    }
+
+    pub fn replace_emojis(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for (name, default_emoji) in EMBED_EMOJIS {
+            if let Some(emoji) = self.emojis.get(name) {
+                text = text.replace(default_emoji, emoji);
+            }
+        }
+        text
+    }
+
+    pub fn fields_to_str(&self) -> String {
+        self.fields.join(", ")
+    }
+
+    pub fn split_colors_to_str(&self) -> String {
+        if self.split_colors.is_empty() {
+            return "off".to_string();
+        }
+        let mut split_colors = self
+            .split_colors
+            .iter()
+            .map(|(split, color)| format!("{}=#{:06x}", split, color))
+            .collect::<Vec<_>>();
+        split_colors.sort();
+        split_colors.join(", ")
+    }
+
+    pub fn split_thumbnails_to_str(&self) -> String {
+        if self.split_thumbnails.is_empty() {
+            return "off".to_string();
+        }
+        let mut split_thumbnails = self
+            .split_thumbnails
+            .iter()
+            .map(|(split, thumbnail)| format!("{}={}", split, thumbnail))
+            .collect::<Vec<_>>();
+        split_thumbnails.sort();
+        split_thumbnails.join(", ")
+    }
+
+    pub fn emojis_to_str(&self) -> String {
+        if self.emojis.is_empty() {
+            return "off".to_string();
+        }
+        let mut emojis = self
+            .emojis
+            .iter()
+            .map(|(name, emoji)| format!("{}={}", name, emoji))
+            .collect::<Vec<_>>();
+        emojis.sort();
+        emojis.join(", ")
+    }
+}
@@ -10,10 +10,11 @@ use serenity::{
 
 use crate::{
     cache::{
-        PlayerCacheEntry, RoleCacheEntry, BASTION_EMOJI, END_EMOJI, FORT_EMOJI, PORTAL_EMOJI,
-        SH_EMOJI,
+        GuildSettings, PingStateEntry, PlayerCacheEntry, RoleCacheEntry, SubscriptionCacheEntry,
+        BASTION_EMOJI, END_EMOJI, FORT_EMOJI, PORTAL_EMOJI, SH_EMOJI,
     },
     config::PACEMANBOT_RUNNER_NAMES_CHANNEL,
+    dispatcher::mins_secs_to_millis,
     ws::EventId,
 };
 
@@ -23,6 +24,9 @@ pub struct GuildCacheEntry {
     pub lb_channel: Option<ChannelId>,
     pub player_whitelist: HashMap<String, PlayerCacheEntry>,
     pub roles: Vec<RoleCacheEntry>,
+    pub settings: GuildSettings,
+    pub subscriptions: Vec<SubscriptionCacheEntry>,
+    pub ping_state: PingStateEntry,
 }
 
 impl GuildCacheEntry {
@@ -40,12 +44,47 @@ impl GuildCacheEntry {
         ))
     }
 
+    // Whitelist key of a runner. Matches by uuid first, so that entries keep
+    // working after a name change.
+    pub fn find_runner(&self, name: &str, uuid: &str) -> Option<String> {
+        let uuid = uuid.to_lowercase();
+        self.player_whitelist
+            .iter()
+            .find(|(key, player_data)| **key == uuid || player_data.uuid.as_ref() == Some(&uuid))
+            .or_else(|| self.player_whitelist.get_key_value(&name.to_lowercase()))
+            .map(|(key, _)| key.to_owned())
+    }
+
+    pub fn split_thresholds(&self, split: &Split, runner: &str) -> Vec<u64> {
+        self.roles
+            .iter()
+            .filter(|role_cache_entry| {
+                &role_cache_entry.split == split
+                    && !role_cache_entry.role.name.contains("PB")
+                    && (role_cache_entry.runner.is_empty()
+                        || role_cache_entry.runner.to_lowercase() == runner.to_lowercase())
+            })
+            .map(|role_cache_entry| {
+                mins_secs_to_millis((role_cache_entry.minutes, role_cache_entry.seconds))
+            })
+            .collect()
+    }
+
     pub fn is_private_from_channels(mut channels: impl Iterator<Item = GuildChannel>) -> bool {
         channels.any(|c| c.name == PACEMANBOT_RUNNER_NAMES_CHANNEL)
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+// Splits in the order of the role selection message.
+pub const SPLITS: [Split; 5] = [
+    Split::FirstStructure,
+    Split::SecondStructure,
+    Split::Blind,
+    Split::EyeSpy,
+    Split::EndEnter,
+];
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum Split {
     FirstStructure,
     SecondStructure,
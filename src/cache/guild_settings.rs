@@ -0,0 +1,403 @@
+use std::error::Error;
+
+use crate::{
+    cache::{EmbedTemplate, PingWindow, RoleMessage, EDIT_MESSAGE_DELAY, MESSAGE_DELETE_DELAY},
+    config::{
+        parse_bool_setting, parse_custom_emojis, parse_embed_fields, parse_live_platforms,
+        parse_number_setting, parse_ping_schedule, parse_quiet_hours, parse_role_messages,
+        parse_split_colors, parse_split_thumbnails, parse_utc_offset,
+    },
+    locale::{Locale, LOCALES},
+    ws::{Platform, PLATFORMS},
+};
+
+pub const SETTING_KEYS: [&str; 22] = [
+    "ping_on_projection",
+    "run_replies",
+    "run_end_notifications",
+    "cheated_run_policy",
+    "utc_offset",
+    "quiet_hours",
+    "ping_schedule",
+    "role_cooldown",
+    "runner_cooldown",
+    "ping_digest",
+    "ping_strip_mode",
+    "ping_strip_delay",
+    "message_delete_delay",
+    "embed_fields",
+    "embed_compact",
+    "split_colors",
+    "pace_colors",
+    "split_thumbnails",
+    "custom_emojis",
+    "locale",
+    "live_platforms",
+    "author_link",
+];
+// Stored with the other settings, but kept up to date by the bot instead of /settings.
+pub const INTERNAL_SETTING_KEYS: [&str; 1] = ["role_messages"];
+
+#[derive(Clone, PartialEq)]
+pub enum CheatedRunPolicy {
+    Suppress,
+    NoPings,
+    Annotate,
+    Allow,
+}
+
+impl CheatedRunPolicy {
+    pub fn from_str(policy: &str) -> Option<CheatedRunPolicy> {
+        match policy {
+            "suppress" => Some(CheatedRunPolicy::Suppress),
+            "no_pings" => Some(CheatedRunPolicy::NoPings),
+            "annotate" => Some(CheatedRunPolicy::Annotate),
+            "allow" => Some(CheatedRunPolicy::Allow),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            CheatedRunPolicy::Suppress => "suppress",
+            CheatedRunPolicy::NoPings => "no_pings",
+            CheatedRunPolicy::Annotate => "annotate",
+            CheatedRunPolicy::Allow => "allow",
+        }
+        .to_string()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum AuthorLink {
+    Stream,
+    Profile,
+    Run,
+    Off,
+}
+
+impl AuthorLink {
+    pub fn from_str(link: &str) -> Option<AuthorLink> {
+        match link {
+            "stream" => Some(AuthorLink::Stream),
+            "profile" => Some(AuthorLink::Profile),
+            "run" => Some(AuthorLink::Run),
+            "off" => Some(AuthorLink::Off),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            AuthorLink::Stream => "stream",
+            AuthorLink::Profile => "profile",
+            AuthorLink::Run => "run",
+            AuthorLink::Off => "off",
+        }
+        .to_string()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum PingStripMode {
+    Keep,
+    Strip,
+    Delete,
+}
+
+impl PingStripMode {
+    pub fn from_str(mode: &str) -> Option<PingStripMode> {
+        match mode {
+            "keep" => Some(PingStripMode::Keep),
+            "strip" => Some(PingStripMode::Strip),
+            "delete" => Some(PingStripMode::Delete),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        match self {
+            PingStripMode::Keep => "keep",
+            PingStripMode::Strip => "strip",
+            PingStripMode::Delete => "delete",
+        }
+        .to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct GuildSettings {
+    pub ping_on_projection: bool,
+    pub run_replies: bool,
+    pub run_end_notifications: bool,
+    pub cheated_run_policy: CheatedRunPolicy,
+    // Offset from UTC in minutes used for `quiet_hours` and `ping_schedule`. It
+    // is fixed, so servers have to update it themselves for daylight saving time.
+    pub utc_offset: i16,
+    pub quiet_hours: Option<(u8, u8)>,
+    pub ping_schedule: Vec<PingWindow>,
+    // Cooldowns in seconds, 0 disables them.
+    pub role_cooldown: u64,
+    pub runner_cooldown: u64,
+    pub ping_digest: bool,
+    pub ping_strip_mode: PingStripMode,
+    // Seconds after which runner specific mentions are stripped in `PingStripMode::Strip`.
+    pub ping_strip_delay: u64,
+    // Minutes after which pace messages are deleted in `PingStripMode::Delete`.
+    pub message_delete_delay: u64,
+    pub embed_template: EmbedTemplate,
+    pub locale: Locale,
+    // Platforms that count as live, both for the live link and for public servers.
+    pub live_platforms: Vec<Platform>,
+    pub author_link: AuthorLink,
+    // Kept up to date by /send_message, these are refreshed when the pace-roles change.
+    pub role_messages: Vec<RoleMessage>,
+}
+
+impl Default for GuildSettings {
+    fn default() -> Self {
+        Self {
+            ping_on_projection: false,
+            run_replies: false,
+            run_end_notifications: false,
+            cheated_run_policy: CheatedRunPolicy::Allow,
+            utc_offset: 0,
+            quiet_hours: None,
+            ping_schedule: Vec::new(),
+            role_cooldown: 0,
+            runner_cooldown: 0,
+            ping_digest: false,
+            ping_strip_mode: PingStripMode::Strip,
+            ping_strip_delay: EDIT_MESSAGE_DELAY,
+            message_delete_delay: MESSAGE_DELETE_DELAY,
+            embed_template: EmbedTemplate::default(),
+            locale: Locale::En,
+            live_platforms: PLATFORMS.to_vec(),
+            author_link: AuthorLink::Stream,
+            role_messages: Vec::new(),
+        }
+    }
+}
+
+impl GuildSettings {
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        match key {
+            "ping_on_projection" => self.ping_on_projection = parse_bool_setting(key, value)?,
+            "run_replies" => self.run_replies = parse_bool_setting(key, value)?,
+            "run_end_notifications" => self.run_end_notifications = parse_bool_setting(key, value)?,
+            "cheated_run_policy" => {
+                self.cheated_run_policy = match CheatedRunPolicy::from_str(value) {
+                    Some(policy) => policy,
+                    None => {
+                        return Err(format!(
+                            "failed to parse value: '{}' for setting: '{}', expected one of 'suppress', 'no_pings', 'annotate' or 'allow'.",
+                            value, key
+                        )
+                        .into())
+                    }
+                }
+            }
+            "utc_offset" => self.utc_offset = parse_utc_offset(key, value)?,
+            "quiet_hours" => {
+                self.quiet_hours = match value.trim().to_lowercase().as_str() {
+                    "off" => None,
+                    _ => Some(parse_quiet_hours(value)?),
+                }
+            }
+            "ping_schedule" => self.ping_schedule = parse_ping_schedule(key, value)?,
+            "role_cooldown" => self.role_cooldown = parse_number_setting(key, value)?,
+            "runner_cooldown" => self.runner_cooldown = parse_number_setting(key, value)?,
+            "ping_digest" => self.ping_digest = parse_bool_setting(key, value)?,
+            "ping_strip_mode" => {
+                self.ping_strip_mode = match PingStripMode::from_str(value) {
+                    Some(mode) => mode,
+                    None => {
+                        return Err(format!(
+                            "failed to parse value: '{}' for setting: '{}', expected one of 'keep', 'strip' or 'delete'.",
+                            value, key
+                        )
+                        .into())
+                    }
+                }
+            }
+            "ping_strip_delay" => self.ping_strip_delay = parse_number_setting(key, value)?,
+            "message_delete_delay" => {
+                self.message_delete_delay = parse_number_setting(key, value)?
+            }
+            "embed_fields" => self.embed_template.fields = parse_embed_fields(key, value)?,
+            "embed_compact" => self.embed_template.compact = parse_bool_setting(key, value)?,
+            "split_colors" => self.embed_template.split_colors = parse_split_colors(key, value)?,
+            "pace_colors" => self.embed_template.pace_colors = parse_bool_setting(key, value)?,
+            "split_thumbnails" => {
+                self.embed_template.split_thumbnails = parse_split_thumbnails(key, value)?
+            }
+            "custom_emojis" => self.embed_template.emojis = parse_custom_emojis(key, value)?,
+            "locale" => {
+                self.locale = match Locale::from_str(value) {
+                    Some(locale) => locale,
+                    None => {
+                        return Err(format!(
+                            "failed to parse value: '{}' for setting: '{}', expected one of {}.",
+                            value,
+                            key,
+                            LOCALES
+                                .iter()
+                                .map(|locale| format!("'{}'", locale.to_str()))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        )
+                        .into())
+                    }
+                }
+            }
+            "live_platforms" => self.live_platforms = parse_live_platforms(key, value)?,
+            "author_link" => {
+                self.author_link = match AuthorLink::from_str(value) {
+                    Some(link) => link,
+                    None => {
+                        return Err(format!(
+                            "failed to parse value: '{}' for setting: '{}', expected one of 'stream', 'profile', 'run' or 'off'.",
+                            value, key
+                        )
+                        .into())
+                    }
+                }
+            }
+            "role_messages" => self.role_messages = parse_role_messages(key, value)?,
+            _ => return Err(format!("unrecognized setting: '{}'.", key).into()),
+        };
+        Ok(())
+    }
+
+    pub fn reset(&mut self, key: &str) -> Result<(), Box<dyn Error>> {
+        let default_settings = GuildSettings::default();
+        let default_value = match default_settings.get(key) {
+            Some(value) => value,
+            None => return Err(format!("unrecognized setting: '{}'.", key).into()),
+        };
+        self.set(key, default_value.as_str())
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        Some(match key {
+            "ping_on_projection" => self.ping_on_projection.to_string(),
+            "run_replies" => self.run_replies.to_string(),
+            "run_end_notifications" => self.run_end_notifications.to_string(),
+            "cheated_run_policy" => self.cheated_run_policy.to_str(),
+            "utc_offset" => format!(
+                "{}{}:{:02}",
+                if self.utc_offset < 0 { "-" } else { "+" },
+                self.utc_offset.abs() / 60,
+                self.utc_offset.abs() % 60
+            ),
+            "quiet_hours" => match self.quiet_hours {
+                Some((start, end)) => format!("{}-{}", start, end),
+                None => "off".to_string(),
+            },
+            "ping_schedule" => {
+                if self.ping_schedule.is_empty() {
+                    "off".to_string()
+                } else {
+                    self.ping_schedule
+                        .iter()
+                        .map(|window| window.to_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            }
+            "role_cooldown" => self.role_cooldown.to_string(),
+            "runner_cooldown" => self.runner_cooldown.to_string(),
+            "ping_digest" => self.ping_digest.to_string(),
+            "ping_strip_mode" => self.ping_strip_mode.to_str(),
+            "ping_strip_delay" => self.ping_strip_delay.to_string(),
+            "message_delete_delay" => self.message_delete_delay.to_string(),
+            "embed_fields" => self.embed_template.fields_to_str(),
+            "embed_compact" => self.embed_template.compact.to_string(),
+            "split_colors" => self.embed_template.split_colors_to_str(),
+            "pace_colors" => self.embed_template.pace_colors.to_string(),
+            "split_thumbnails" => self.embed_template.split_thumbnails_to_str(),
+            "custom_emojis" => self.embed_template.emojis_to_str(),
+            "locale" => self.locale.to_str().to_string(),
+            "live_platforms" => self
+                .live_platforms
+                .iter()
+                .map(|platform| platform.to_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+            "author_link" => self.author_link.to_str(),
+            "role_messages" => {
+                if self.role_messages.is_empty() {
+                    "off".to_string()
+                } else {
+                    self.role_messages
+                        .iter()
+                        .map(|role_message| role_message.to_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            }
+            _ => return None,
+        })
+    }
+
+    pub fn local_hour(&self, timestamp: u64) -> u8 {
+        let local_timestamp = timestamp as i64 + self.utc_offset as i64 * 60;
+        local_timestamp.div_euclid(3600).rem_euclid(24) as u8
+    }
+
+    pub fn is_ping_allowed(&self, timestamp: u64) -> bool {
+        let local_timestamp = timestamp as i64 + self.utc_offset as i64 * 60;
+        let hour = self.local_hour(timestamp);
+        // 1970-01-01 was a thursday, which is index 3 in `WEEKDAYS`.
+        let weekday = (local_timestamp.div_euclid(86400) + 3).rem_euclid(7) as u8;
+        let is_quiet = match self.quiet_hours {
+            Some((start, end)) => PingWindow {
+                weekday: None,
+                start,
+                end,
+            }
+            .contains(weekday, hour),
+            None => false,
+        };
+        let is_scheduled = self.ping_schedule.is_empty()
+            || self
+                .ping_schedule
+                .iter()
+                .any(|window| window.contains(weekday, hour));
+        !is_quiet && is_scheduled
+    }
+
+    pub fn to_config_contents(&self) -> String {
+        SETTING_KEYS
+            .iter()
+            .chain(INTERNAL_SETTING_KEYS.iter())
+            .filter_map(|key| self.get(key).map(|value| format!("{}={}", key, value)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pings_follow_the_utc_offset() {
+        // 1970-01-03 was a saturday.
+        let saturday_23_utc = 2 * 86400 + 23 * 3600;
+        let mut settings = GuildSettings::default();
+        settings.set("ping_schedule", "sat 22-2").unwrap();
+        assert!(settings.is_ping_allowed(saturday_23_utc));
+
+        settings.set("utc_offset", "-5").unwrap();
+        assert_eq!(settings.local_hour(saturday_23_utc), 18);
+        assert!(!settings.is_ping_allowed(saturday_23_utc));
+
+        settings.set("utc_offset", "+2").unwrap();
+        assert_eq!(settings.local_hour(saturday_23_utc), 1);
+        assert!(settings.is_ping_allowed(saturday_23_utc));
+
+        settings.set("quiet_hours", "0-8").unwrap();
+        assert!(!settings.is_ping_allowed(saturday_23_utc));
+    }
+}
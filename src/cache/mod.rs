@@ -1,11 +1,35 @@
 mod cache;
+mod config_history_entry;
+mod config_snapshot;
 mod consts;
+mod embed_template;
 mod guild_cache_entry;
+mod guild_settings;
+mod pace_history_entry;
+mod pending_import;
+mod ping_state_entry;
+mod ping_window;
 mod player_cache_entry;
 mod role_cache_entry;
+mod role_message;
+mod run_cache_entry;
+mod subscription_cache_entry;
 
 pub use cache::Cache;
+pub use config_history_entry::ConfigHistoryEntry;
+pub use config_snapshot::ConfigSnapshot;
 pub use consts::*;
-pub use guild_cache_entry::{GuildCacheEntry, Split, Structure};
+pub use embed_template::{EmbedTemplate, EMBED_EMOJIS, EMBED_FIELDS};
+pub use guild_cache_entry::{GuildCacheEntry, Split, Structure, SPLITS};
+pub use guild_settings::{
+    AuthorLink, CheatedRunPolicy, GuildSettings, PingStripMode, INTERNAL_SETTING_KEYS, SETTING_KEYS,
+};
+pub use pace_history_entry::PaceHistoryEntry;
+pub use pending_import::PendingImport;
+pub use ping_state_entry::PingStateEntry;
+pub use ping_window::{PingWindow, WEEKDAYS};
 pub use player_cache_entry::PlayerCacheEntry;
 pub use role_cache_entry::RoleCacheEntry;
+pub use role_message::RoleMessage;
+pub use run_cache_entry::RunCacheEntry;
+pub use subscription_cache_entry::{SubscriptionCacheEntry, SubscriptionDelivery};
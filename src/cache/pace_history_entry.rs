@@ -0,0 +1,36 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Instant,
+};
+
+use crate::cache::{Split, PACE_HISTORY_SIZE};
+
+#[derive(Clone)]
+pub struct PaceHistoryEntry {
+    pub finish_deltas: HashMap<Split, VecDeque<u64>>,
+    pub last_recorded: Instant,
+}
+
+impl Default for PaceHistoryEntry {
+    fn default() -> Self {
+        Self {
+            finish_deltas: HashMap::new(),
+            last_recorded: Instant::now(),
+        }
+    }
+}
+
+impl PaceHistoryEntry {
+    pub fn record(&mut self, split: Split, finish_delta: u64) {
+        self.last_recorded = Instant::now();
+        let deltas = self.finish_deltas.entry(split).or_default();
+        deltas.push_back(finish_delta);
+        while deltas.len() > PACE_HISTORY_SIZE {
+            deltas.pop_front();
+        }
+    }
+
+    pub fn get(&self, split: &Split) -> Option<&VecDeque<u64>> {
+        self.finish_deltas.get(split)
+    }
+}
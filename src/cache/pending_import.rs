@@ -0,0 +1,18 @@
+use std::{collections::HashMap, time::Instant};
+
+use crate::cache::{PlayerCacheEntry, PENDING_IMPORT_TTL};
+
+// A validated whitelist import waiting for the confirm button.
+pub struct PendingImport {
+    pub players: HashMap<String, PlayerCacheEntry>,
+    // Contents of the whitelist the preview was made against, the import is
+    // refused if it changed in the meantime.
+    pub base_contents: String,
+    pub created_at: Instant,
+}
+
+impl PendingImport {
+    pub fn is_expired(&self) -> bool {
+        self.created_at.elapsed().as_secs() > PENDING_IMPORT_TTL
+    }
+}
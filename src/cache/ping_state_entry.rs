@@ -0,0 +1,44 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serenity::model::id::{RoleId, UserId};
+
+// State of recent pings in a guild, kept across config reloads.
+#[derive(Default)]
+pub struct PingStateEntry {
+    pub dm_failures: HashMap<UserId, u8>,
+    pub role_pings: HashMap<RoleId, Instant>,
+    pub runner_pings: HashMap<String, Instant>,
+    // (metadata, roles) of pings that were dropped during a cooldown.
+    pub pending_digest: Vec<(String, Vec<RoleId>)>,
+}
+
+impl PingStateEntry {
+    pub fn is_role_on_cooldown(&self, role_id: &RoleId, cooldown: u64) -> bool {
+        cooldown > 0
+            && self
+                .role_pings
+                .get(role_id)
+                .is_some_and(|pinged_at| pinged_at.elapsed() < Duration::from_secs(cooldown))
+    }
+
+    pub fn is_runner_on_cooldown(&self, runner: &str, cooldown: u64) -> bool {
+        cooldown > 0
+            && self
+                .runner_pings
+                .get(&runner.to_lowercase())
+                .is_some_and(|pinged_at| pinged_at.elapsed() < Duration::from_secs(cooldown))
+    }
+
+    pub fn record_pings(&mut self, runner: &str, role_ids: &[RoleId]) {
+        let now = Instant::now();
+        for role_id in role_ids {
+            self.role_pings.insert(role_id.to_owned(), now);
+        }
+        if !role_ids.is_empty() {
+            self.runner_pings.insert(runner.to_lowercase(), now);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+pub const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+#[derive(Clone, PartialEq)]
+pub struct PingWindow {
+    // Index into `WEEKDAYS`, `None` if the window applies to every day.
+    pub weekday: Option<u8>,
+    pub start: u8,
+    pub end: u8,
+}
+
+impl PingWindow {
+    pub fn contains(&self, weekday: u8, hour: u8) -> bool {
+        let previous_weekday = (weekday + 6) % 7;
+        let is_day = |day: u8| self.weekday.is_none() || self.weekday == Some(day);
+        if self.start <= self.end {
+            is_day(weekday) && hour >= self.start && hour < self.end
+        } else {
+            // Windows like `sat 22-2` run past midnight into the next day.
+            (is_day(weekday) && hour >= self.start) || (is_day(previous_weekday) && hour < self.end)
+        }
+    }
+
+    pub fn to_str(&self) -> String {
+        let day = match self.weekday {
+            Some(weekday) => WEEKDAYS[weekday as usize],
+            None => "daily",
+        };
+        format!("{} {}-{}", day, self.start, self.end)
+    }
+}
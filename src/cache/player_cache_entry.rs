@@ -1,6 +1,9 @@
-use crate::cache::Split;
+use crate::{
+    cache::{Split, SPLITS},
+    locale::Locale,
+};
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct PlayerCacheEntry {
     pub first_structure: u8,
     pub second_structure: u8,
@@ -8,6 +11,9 @@ pub struct PlayerCacheEntry {
     pub eye_spy: u8,
     pub end_enter: u8,
     pub finish: Option<u8>,
+    pub display_name: Option<String>,
+    // Set when the runner is whitelisted by name and their uuid is known.
+    pub uuid: Option<String>,
 }
 
 impl Default for PlayerCacheEntry {
@@ -19,6 +25,8 @@ impl Default for PlayerCacheEntry {
             eye_spy: 0,
             end_enter: 0,
             finish: None,
+            display_name: None,
+            uuid: None,
         }
     }
 }
@@ -33,4 +41,32 @@ impl PlayerCacheEntry {
             Split::EndEnter => Some(self.end_enter),
         }
     }
+
+    pub fn thresholds_to_str(&self, locale: Locale) -> String {
+        let format_threshold = |minutes: u8| {
+            if minutes == 0 {
+                locale.tr("off")
+            } else {
+                locale.trf("sub {0}:00", &[minutes.to_string().as_str()])
+            }
+        };
+        let mut thresholds = SPLITS
+            .iter()
+            .map(|split| {
+                format!(
+                    "{}: {}",
+                    locale.tr(split.alt_desc().as_str()),
+                    format_threshold(self.get(split).unwrap_or_default())
+                )
+            })
+            .collect::<Vec<_>>();
+        if let Some(finish) = self.finish {
+            thresholds.push(format!(
+                "{}: {}",
+                locale.tr("Finish"),
+                format_threshold(finish)
+            ));
+        }
+        thresholds.join(" · ")
+    }
 }
@@ -5,7 +5,7 @@ use serenity::model::guild::Role;
 use crate::{
     cache::{PlayerCacheEntry, Split},
     config::Config,
-    dispatcher::{millis_to_mins_secs, RunInfo},
+    dispatcher::{millis_to_mins_secs, Projection, RunInfo},
     ws::{Event, WSResponse},
 };
 
@@ -30,8 +30,13 @@ impl RoleCacheEntry {
         last_event: &Event,
         is_private: bool,
         ws_response: &WSResponse,
+        projection: Option<&Projection>,
     ) -> bool {
-        let (split_minutes, split_seconds) = millis_to_mins_secs(last_event.igt as u64);
+        let split_time = match projection {
+            Some(projection) => projection.equivalent_split_time(),
+            None => last_event.igt as u64,
+        };
+        let (split_minutes, split_seconds) = millis_to_mins_secs(split_time);
         if self.role.name.contains("PB") {
             if !is_private {
                 return false;
@@ -0,0 +1,22 @@
+use serenity::model::id::{ChannelId, MessageId};
+
+// Role selection messages sent by one /send_message, in the order they were sent.
+#[derive(Clone, PartialEq)]
+pub struct RoleMessage {
+    pub channel_id: ChannelId,
+    pub message_ids: Vec<MessageId>,
+}
+
+impl RoleMessage {
+    pub fn to_str(&self) -> String {
+        format!(
+            "{}:{}",
+            self.channel_id,
+            self.message_ids
+                .iter()
+                .map(|message_id| message_id.to_string())
+                .collect::<Vec<_>>()
+                .join("+")
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serenity::model::{
+    channel::Message,
+    id::{ChannelId, GuildId, MessageId},
+};
+
+use crate::{
+    cache::RUN_CACHE_TTL,
+    ws::{Event, EventId},
+};
+
+pub struct RunCacheEntry {
+    pub nickname: String,
+    pub messages: HashMap<GuildId, Vec<(ChannelId, MessageId)>>,
+    pub last_event: Option<Event>,
+    pub is_ended: bool,
+    // Set on credits, the runner leaving the world after that doesn't end the run.
+    pub is_finished: bool,
+    pub last_updated: Instant,
+}
+
+impl RunCacheEntry {
+    pub fn new(nickname: String) -> Self {
+        Self {
+            nickname,
+            messages: HashMap::new(),
+            last_event: None,
+            is_ended: false,
+            is_finished: false,
+            last_updated: Instant::now(),
+        }
+    }
+
+    pub fn update(&mut self, last_event: &Event) {
+        match last_event.event_id {
+            EventId::CommonLeaveWorld => self.is_ended = true,
+            EventId::CommonRejoinWorld => self.is_ended = false,
+            EventId::RsgCredits => self.is_finished = true,
+            _ => (),
+        };
+        self.last_event = Some(last_event.to_owned());
+        self.last_updated = Instant::now();
+    }
+
+    pub fn is_out_of_order(&self, event: &Event) -> bool {
+        match self.last_event {
+            Some(last_event) => event.igt < last_event.igt,
+            None => false,
+        }
+    }
+
+    pub fn record_message(&mut self, guild_id: GuildId, message: &Message) {
+        self.messages
+            .entry(guild_id)
+            .or_default()
+            .push((message.channel_id, message.id));
+    }
+
+    pub fn last_message(&self, guild_id: &GuildId) -> Option<(ChannelId, MessageId)> {
+        self.messages
+            .get(guild_id)
+            .and_then(|messages| messages.last())
+            .map(|message| message.to_owned())
+    }
+
+    pub fn is_stale(&self) -> bool {
+        self.last_updated.elapsed() > Duration::from_secs(RUN_CACHE_TTL)
+    }
+}
@@ -0,0 +1,108 @@
+use serenity::model::id::UserId;
+
+use crate::{
+    cache::Split,
+    dispatcher::{millis_to_mins_secs, Projection, RunInfo},
+    ws::{Event, WSResponse},
+};
+
+#[derive(Clone, PartialEq)]
+pub enum SubscriptionDelivery {
+    Mention,
+    Dm,
+    // DMs to the user failed too many times, kept so that the user can see why they stopped.
+    Disabled,
+}
+
+impl SubscriptionDelivery {
+    pub fn from_str(delivery: &str) -> Option<SubscriptionDelivery> {
+        match delivery {
+            "mention" => Some(SubscriptionDelivery::Mention),
+            "dm" => Some(SubscriptionDelivery::Dm),
+            "disabled" => Some(SubscriptionDelivery::Disabled),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            SubscriptionDelivery::Mention => "mention",
+            SubscriptionDelivery::Dm => "dm",
+            SubscriptionDelivery::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SubscriptionCacheEntry {
+    pub user_id: UserId,
+    pub runner: String,
+    pub split: Split,
+    pub minutes: u8,
+    pub seconds: u8,
+    pub delivery: SubscriptionDelivery,
+    // (start, end) hours in the server's `utc_offset` during which the user is not notified.
+    pub quiet_hours: Option<(u8, u8)>,
+}
+
+impl SubscriptionCacheEntry {
+    pub fn is_notifiable(
+        &self,
+        run_info: &RunInfo,
+        last_event: &Event,
+        ws_response: &WSResponse,
+        projection: Option<&Projection>,
+        current_hour: u8,
+    ) -> bool {
+        let split_time = match projection {
+            Some(projection) => projection.equivalent_split_time(),
+            None => last_event.igt as u64,
+        };
+        let (split_minutes, split_seconds) = millis_to_mins_secs(split_time);
+        self.delivery != SubscriptionDelivery::Disabled
+            && !self.is_quiet(current_hour)
+            && self.split == run_info.split
+            && self.runner.to_lowercase() == ws_response.nickname.to_lowercase()
+            && self.minutes >= split_minutes
+            && (self.minutes != split_minutes || self.seconds > split_seconds)
+    }
+
+    pub fn is_quiet(&self, current_hour: u8) -> bool {
+        match self.quiet_hours {
+            Some((start, end)) if start <= end => current_hour >= start && current_hour < end,
+            Some((start, end)) => current_hour >= start || current_hour < end,
+            None => false,
+        }
+    }
+
+    pub fn to_config_line(&self) -> String {
+        let mut line = format!(
+            "{}:{}/{}/{}/{}/{}",
+            self.user_id,
+            self.runner,
+            self.split.to_str(),
+            self.minutes,
+            self.seconds,
+            self.delivery.to_str(),
+        );
+        if let Some((start, end)) = self.quiet_hours {
+            line.push_str(format!("/{}-{}", start, end).as_str());
+        }
+        line
+    }
+
+    pub fn to_formatted_message(&self) -> String {
+        let mut message = format!(
+            "{} - {} sub {}:{:02} ({})",
+            self.runner,
+            self.split.alt_desc(),
+            self.minutes,
+            self.seconds,
+            self.delivery.to_str(),
+        );
+        if let Some((start, end)) = self.quiet_hours {
+            message.push_str(format!(", quiet from {}:00 to {}:00", start, end).as_str());
+        }
+        message
+    }
+}
@@ -0,0 +1,230 @@
+use std::{borrow::Cow, collections::VecDeque, error::Error, sync::Arc};
+
+use serenity::{
+    builder::CreateEmbed,
+    client::Context,
+    futures::lock::Mutex,
+    model::{
+        channel::{AttachmentType, GuildChannel},
+        id::GuildId,
+        prelude::application_command::{CommandDataOption, CommandDataOptionValue},
+        user::User,
+        Timestamp,
+    },
+};
+
+use crate::{
+    cache::{
+        Cache, ConfigHistoryEntry, ConfigSnapshot, CONFIG_HISTORY_SIZE, INTERNAL_SETTING_KEYS,
+    },
+    command::{
+        get_config_lines, get_config_message, CONFIG_HISTORY_FILE_PREFIX, MAX_AUDIT_FIELD_LENGTH,
+    },
+    config::{
+        extract_setting_from_config_line, PACEMANBOT_AUDIT_CHANNEL,
+        PACEMANBOT_RUNNER_NAMES_CHANNEL, PACEMANBOT_SETTINGS_CHANNEL, ROLE_PREFIX, ROLE_PREFIX_115,
+        ROLE_PREFIX_17, ROLE_PREFIX_AA,
+    },
+};
+
+// Keys the bot maintains itself, like role_messages, aren't config changes.
+pub fn is_internal_setting(line: &str) -> bool {
+    match extract_setting_from_config_line(line) {
+        Ok((key, _)) => INTERNAL_SETTING_KEYS.contains(&key.as_str()),
+        Err(_) => false,
+    }
+}
+
+// Config channels that don't exist are left empty.
+pub async fn get_config_snapshot(
+    ctx: &Context,
+    guild_id: &GuildId,
+) -> Result<ConfigSnapshot, Box<dyn Error>> {
+    let mut snapshot = ConfigSnapshot::default();
+    if let Ok((_, message)) =
+        get_config_message(ctx, guild_id, PACEMANBOT_RUNNER_NAMES_CHANNEL).await
+    {
+        snapshot.whitelist = get_config_lines(&message);
+    }
+    if let Ok((_, message)) = get_config_message(ctx, guild_id, PACEMANBOT_SETTINGS_CHANNEL).await {
+        snapshot.settings = get_config_lines(&message)
+            .into_iter()
+            .filter(|line| !is_internal_setting(line))
+            .collect();
+    }
+    snapshot.roles = guild_id
+        .roles(&ctx.http)
+        .await?
+        .into_values()
+        .map(|role| role.name)
+        .filter(|name| {
+            name.starts_with(ROLE_PREFIX)
+                && !name.starts_with(ROLE_PREFIX_115)
+                && !name.starts_with(ROLE_PREFIX_17)
+                && !name.starts_with(ROLE_PREFIX_AA)
+        })
+        .collect();
+    snapshot.roles.sort();
+    Ok(snapshot)
+}
+
+// Options as they would be typed, e.g. `add_or_update ign:Its_Saanvi blind:5`.
+pub fn format_options(options: &[CommandDataOption]) -> String {
+    options
+        .iter()
+        .map(|option| {
+            if !option.options.is_empty() {
+                return format!("{} {}", option.name, format_options(&option.options));
+            }
+            match (option.resolved.as_ref(), option.value.as_ref()) {
+                (Some(CommandDataOptionValue::Attachment(attachment)), _) => {
+                    format!("{}:{}", option.name, attachment.filename)
+                }
+                (_, Some(value)) => match value.as_str() {
+                    Some(str) => format!("{}:{}", option.name, str),
+                    None => format!("{}:{}", option.name, value),
+                },
+                (_, None) => option.name.to_owned(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn diff_field_value(diff: &[String]) -> String {
+    let mut value = String::new();
+    for line in diff {
+        // Leaves room for the code block and the note about the omitted lines.
+        if value.len() + line.len() > MAX_AUDIT_FIELD_LENGTH - 40 {
+            value.push_str(
+                format!("... {} more lines\n", diff.len() - value.lines().count()).as_str(),
+            );
+            break;
+        }
+        value.push_str(format!("{}\n", line).as_str());
+    }
+    format!("```diff\n{}```", value)
+}
+
+fn get_audit_channel(ctx: &Context, guild_id: &GuildId) -> Option<GuildChannel> {
+    ctx.cache.guild_channels(guild_id).and_then(|channels| {
+        channels
+            .iter()
+            .find(|channel| channel.name == PACEMANBOT_AUDIT_CHANNEL)
+            .map(|channel| channel.value().to_owned())
+    })
+}
+
+fn create_audit_embed(
+    user: &User,
+    action: &str,
+    before: &ConfigSnapshot,
+    after: &ConfigSnapshot,
+    error: Option<&str>,
+) -> CreateEmbed {
+    let mut embed = CreateEmbed::default();
+    embed
+        .author(|a| a.name(user.tag()).icon_url(user.face()))
+        .description(format!("`/{}`", action))
+        .footer(|f| f.text(format!("User id: {}", user.id)))
+        .timestamp(Timestamp::now());
+    for (name, before, after) in [
+        ("Whitelist", &before.whitelist, &after.whitelist),
+        ("Pace-roles", &before.roles, &after.roles),
+        ("Settings", &before.settings, &after.settings),
+    ] {
+        let diff = ConfigSnapshot::diff_lines(before, after);
+        if !diff.is_empty() {
+            embed.field(name, diff_field_value(&diff), false);
+        }
+    }
+    if let Some(error) = error {
+        embed.field(
+            "Error",
+            error
+                .chars()
+                .take(MAX_AUDIT_FIELD_LENGTH)
+                .collect::<String>(),
+            false,
+        );
+    }
+    embed
+}
+
+// Adds the change to the server's history so it can be reverted with
+// /config_revert, and posts it to #pacemanbot-audit if the server has one. The
+// history entry is attached to the message, so that it survives restarts.
+pub async fn record_config_change(
+    ctx: &Context,
+    guild_id: &GuildId,
+    cache: Arc<Mutex<Cache>>,
+    user: &User,
+    action: &str,
+    before: ConfigSnapshot,
+    error: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let after = get_config_snapshot(ctx, guild_id).await?;
+    let mut history_file = None;
+    if after != before {
+        let mut locked_cache = cache.lock().await;
+        let history = locked_cache.config_history.entry(*guild_id).or_default();
+        let entry = ConfigHistoryEntry {
+            id: history.back().map_or(1, |entry| entry.id + 1),
+            user_id: user.id,
+            action: action.to_string(),
+            before: before.clone(),
+            after: after.clone(),
+            created_at: Timestamp::now(),
+        };
+        history_file = Some(AttachmentType::Bytes {
+            data: Cow::from(serde_json::to_vec(&entry)?),
+            filename: format!("{}{}.json", CONFIG_HISTORY_FILE_PREFIX, entry.id),
+        });
+        history.push_back(entry);
+        if history.len() > CONFIG_HISTORY_SIZE {
+            history.pop_front();
+        }
+    }
+
+    let audit_channel = match get_audit_channel(ctx, guild_id) {
+        Some(channel) => channel,
+        None => return Ok(()),
+    };
+    let embed = create_audit_embed(user, action, &before, &after, error);
+    audit_channel
+        .send_message(&ctx.http, |m| {
+            m.set_embed(embed);
+            if let Some(history_file) = history_file {
+                m.add_file(history_file);
+            }
+            m
+        })
+        .await?;
+    Ok(())
+}
+
+// Reads the config history back from the files attached to #pacemanbot-audit.
+pub async fn load_config_history(
+    ctx: &Context,
+    guild_id: &GuildId,
+) -> Result<VecDeque<ConfigHistoryEntry>, Box<dyn Error>> {
+    let mut history = VecDeque::new();
+    let audit_channel = match get_audit_channel(ctx, guild_id) {
+        Some(channel) => channel,
+        None => return Ok(history),
+    };
+    let bot_id = ctx.cache.current_user_id();
+    let messages = audit_channel.messages(&ctx.http, |m| m.limit(100)).await?;
+    let attachments = messages
+        .iter()
+        .filter(|message| message.author.id == bot_id)
+        .flat_map(|message| message.attachments.iter())
+        .filter(|attachment| attachment.filename.starts_with(CONFIG_HISTORY_FILE_PREFIX))
+        .take(CONFIG_HISTORY_SIZE);
+    // Messages are newest first.
+    for attachment in attachments {
+        let contents = attachment.download().await?;
+        history.push_front(serde_json::from_slice::<ConfigHistoryEntry>(&contents)?);
+    }
+    Ok(history)
+}
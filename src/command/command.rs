@@ -1,16 +1,42 @@
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
 use serenity::{
     async_trait,
     builder::CreateApplicationCommand,
     client::Context,
-    model::{id::GuildId, prelude::application_command::ApplicationCommandInteraction},
+    futures::lock::Mutex,
+    model::{
+        id::GuildId,
+        permissions::Permissions,
+        prelude::{
+            application_command::ApplicationCommandInteraction,
+            autocomplete::AutocompleteInteraction,
+        },
+    },
 };
 
+use super::CommandOptions;
+use crate::{cache::Cache, resolver::UuidResolver};
+
 pub struct CommandContext<'a> {
     pub ctx: &'a Context,
     pub guild_id: GuildId,
     pub interaction: &'a ApplicationCommandInteraction,
+    pub cache: Arc<Mutex<Cache>>,
+    pub resolver: Arc<dyn UuidResolver>,
+}
+
+impl<'a> CommandContext<'a> {
+    pub fn options(&self) -> CommandOptions<'a> {
+        CommandOptions::new(&self.interaction.data.options)
+    }
+}
+
+pub struct AutocompleteContext<'a> {
+    pub ctx: &'a Context,
+    pub guild_id: GuildId,
+    pub interaction: &'a AutocompleteInteraction,
+    pub cache: Arc<Mutex<Cache>>,
 }
 
 #[async_trait]
@@ -21,5 +47,19 @@ pub trait Command: Send + Sync {
         &self,
         command: &'a mut CreateApplicationCommand,
     ) -> &'a mut CreateApplicationCommand;
+    // Commands change the server's configuration, so they are limited to admins
+    // unless a command opts out. Server admins can still override this.
+    fn default_member_permissions(&self) -> Option<Permissions> {
+        Some(Permissions::ADMINISTRATOR)
+    }
+    // Commands that change the configuration are kept in /config_history and get
+    // an entry in #pacemanbot-audit.
+    fn changes_config(&self, _options: CommandOptions<'_>) -> bool {
+        true
+    }
     async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>>;
+    // Only called for options created with `set_autocomplete(true)`.
+    async fn autocomplete(&self, _context: AutocompleteContext<'_>) -> Result<(), Box<dyn Error>> {
+        Ok(())
+    }
 }
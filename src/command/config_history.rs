@@ -0,0 +1,94 @@
+use std::error::Error;
+
+use serenity::{async_trait, builder::CreateApplicationCommand};
+
+use crate::command::{
+    get_guild_locale, Command, CommandContext, CommandOptions, MAX_HISTORY_ACTION_LENGTH,
+};
+
+pub struct ConfigHistory;
+
+#[async_trait]
+impl Command for ConfigHistory {
+    fn name(&self) -> &str {
+        "config_history"
+    }
+
+    fn description(&self) -> &str {
+        "List the recent configuration changes that can be reverted with /config_revert."
+    }
+
+    fn changes_config(&self, _options: CommandOptions<'_>) -> bool {
+        false
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+
+        let lines = match context
+            .cache
+            .lock()
+            .await
+            .config_history
+            .get(&context.guild_id)
+        {
+            Some(history) => history
+                .iter()
+                .rev()
+                .map(|entry| {
+                    let mut action = entry.action.to_owned();
+                    if action.chars().count() > MAX_HISTORY_ACTION_LENGTH {
+                        action = format!(
+                            "{}...",
+                            action
+                                .chars()
+                                .take(MAX_HISTORY_ACTION_LENGTH)
+                                .collect::<String>()
+                        );
+                    }
+                    format!(
+                        "`#{}` <t:{}:R> <@{}> `/{}`\n{}",
+                        entry.id,
+                        entry.created_at.unix_timestamp(),
+                        entry.user_id,
+                        action,
+                        entry.summary()
+                    )
+                })
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        if lines.is_empty() {
+            command
+                .edit_original_interaction_response(&ctx.http, |m| {
+                    m.content(locale.tr("No configuration changes were recorded yet."))
+                })
+                .await?;
+            return Ok(());
+        }
+
+        command
+            .edit_original_interaction_response(&ctx.http, |m| {
+                m.embed(|e| {
+                    e.title(locale.tr("Configuration history"))
+                        .description(lines.join("\n"))
+                        .footer(|f| {
+                            f.text(locale.tr("Use /config_revert with an id to restore the configuration from before that change."))
+                        })
+                })
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub const CONFIG_HISTORY: ConfigHistory = ConfigHistory {};
@@ -0,0 +1,188 @@
+use std::error::Error;
+
+use serde_json::Value;
+use serenity::{
+    async_trait, builder::CreateApplicationCommand, model::prelude::command::CommandOptionType,
+};
+
+use crate::{
+    command::{
+        create_guild_role, get_config_lines, get_config_message, get_config_snapshot,
+        get_focused_option, get_guild_locale, is_internal_setting, set_config_message_contents,
+        AutocompleteContext, Command, CommandContext, MAX_CHOICE_NAME_LENGTH,
+    },
+    config::{PACEMANBOT_RUNNER_NAMES_CHANNEL, PACEMANBOT_SETTINGS_CHANNEL},
+};
+
+pub struct ConfigRevert;
+
+#[async_trait]
+impl Command for ConfigRevert {
+    fn name(&self) -> &str {
+        "config_revert"
+    }
+
+    fn description(&self) -> &str {
+        "Restore the whitelist, pace-roles and settings from before a change."
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.create_option(|option| {
+            option
+                .name("id")
+                .description("Id of the change in /config_history.")
+                .kind(CommandOptionType::Integer)
+                .min_int_value(1)
+                .required(true)
+                .set_autocomplete(true)
+        })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+        let id = match context.options().integer("id")? {
+            Some(id) => id,
+            None => return Err("failed to get value for id option.".into()),
+        };
+
+        let target = match context
+            .cache
+            .lock()
+            .await
+            .config_history
+            .get(&context.guild_id)
+        {
+            Some(history) => history
+                .iter()
+                .find(|entry| i64::from(entry.id) == id)
+                .map(|entry| entry.before.clone()),
+            None => None,
+        };
+        let target = match target {
+            Some(target) => target,
+            None => {
+                return Err(format!(
+                    "no change with id: {}, see /config_history for the recent changes.",
+                    id
+                )
+                .into())
+            }
+        };
+        let current = get_config_snapshot(ctx, &context.guild_id).await?;
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+
+        let mut restored = Vec::new();
+        if current.whitelist != target.whitelist {
+            let (channel, message) =
+                get_config_message(ctx, &context.guild_id, PACEMANBOT_RUNNER_NAMES_CHANNEL).await?;
+            set_config_message_contents(ctx, &channel, message, target.whitelist.join("\n"))
+                .await?;
+            restored.push(locale.trf(
+                "Restored the whitelist to {0} lines.",
+                &[target.whitelist.len().to_string().as_str()],
+            ));
+        }
+
+        if current.settings != target.settings {
+            let (channel, message) =
+                get_config_message(ctx, &context.guild_id, PACEMANBOT_SETTINGS_CHANNEL).await?;
+            // Settings the bot maintains itself, like role_messages, are kept as they are now.
+            let mut settings = target.settings.to_owned();
+            settings.extend(
+                get_config_lines(&message)
+                    .into_iter()
+                    .filter(|line| is_internal_setting(line)),
+            );
+            set_config_message_contents(ctx, &channel, message, settings.join("\n")).await?;
+            restored.push(locale.trf(
+                "Restored the settings to {0} lines.",
+                &[target.settings.len().to_string().as_str()],
+            ));
+        }
+
+        let mut errors = Vec::new();
+        let mut recreated = Vec::new();
+        for role_name in target
+            .roles
+            .iter()
+            .filter(|role_name| !current.roles.contains(role_name))
+        {
+            match create_guild_role(ctx, &context.guild_id, role_name).await {
+                Ok(_) => recreated.push(role_name.to_owned()),
+                Err(err) => errors.push(format!("failed to create role: '{}': {}", role_name, err)),
+            };
+        }
+        if !recreated.is_empty() {
+            restored.push(locale.trf(
+                "Recreated {0} pace-roles: {1}. Members need to pick them again.",
+                &[
+                    recreated.len().to_string().as_str(),
+                    recreated.join(", ").as_str(),
+                ],
+            ));
+        }
+
+        let content = if restored.is_empty() {
+            locale.trf(
+                "The configuration already matches how it was before change #{0}.",
+                &[id.to_string().as_str()],
+            )
+        } else {
+            restored.join("\n")
+        };
+        command
+            .edit_original_interaction_response(&ctx.http, |m| m.content(content))
+            .await?;
+        if !errors.is_empty() {
+            return Err(format!("succeded but with some errors: {}", errors.join("\n\t")).into());
+        }
+        Ok(())
+    }
+
+    async fn autocomplete(&self, context: AutocompleteContext<'_>) -> Result<(), Box<dyn Error>> {
+        let partial = match get_focused_option(&context.interaction.data.options)
+            .and_then(|focused| focused.value.as_ref())
+        {
+            Some(Value::String(value)) => value.to_owned(),
+            Some(value) => value.to_string(),
+            None => String::new(),
+        };
+        let choices = match context
+            .cache
+            .lock()
+            .await
+            .config_history
+            .get(&context.guild_id)
+        {
+            Some(history) => history
+                .iter()
+                .rev()
+                .filter(|entry| entry.id.to_string().starts_with(partial.as_str()))
+                .map(|entry| {
+                    let name = format!("#{} /{}", entry.id, entry.action)
+                        .chars()
+                        .take(MAX_CHOICE_NAME_LENGTH)
+                        .collect::<String>();
+                    (name, entry.id)
+                })
+                .collect::<Vec<_>>(),
+            None => Vec::new(),
+        };
+        context
+            .interaction
+            .create_autocomplete_response(&context.ctx.http, |r| {
+                for (name, id) in choices {
+                    r.add_int_choice(name, i64::from(id));
+                }
+                r
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub const CONFIG_REVERT: ConfigRevert = ConfigRevert {};
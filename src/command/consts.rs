@@ -1,3 +1,27 @@
 use serenity::utils::Colour;
 
 pub const ROLE_COLOR: u32 = Colour::from_rgb(54, 57, 63).0;
+pub const WHITELIST_PAGE_SIZE: usize = 10;
+// Discord shows at most 25 autocomplete choices.
+pub const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+// Button custom ids are `whitelist_page:<page>:<search>`.
+pub const WHITELIST_PAGE_CUSTOM_ID_PREFIX: &str = "whitelist_page:";
+// Keeps the custom id under discord's limit of 100 characters.
+pub const MAX_WHITELIST_SEARCH_LENGTH: usize = 64;
+// Discord limits select menus to 25 options and messages to 5 action rows.
+pub const MAX_SELECT_MENU_OPTIONS: usize = 25;
+pub const MAX_MESSAGE_ACTION_ROWS: usize = 5;
+// Opens the modal instead of replying, so it isn't deferred.
+pub const SET_PINGS_CUSTOM_ID: &str = "set_pings";
+pub const SET_PINGS_MODAL_CUSTOM_ID: &str = "set_pings_modal";
+// Discord limits embed field values to 1024 characters.
+pub const MAX_AUDIT_FIELD_LENGTH: usize = 1024;
+// Discord limits text input placeholders to 100 characters.
+pub const MAX_INPUT_PLACEHOLDER_LENGTH: usize = 100;
+// Discord limits autocomplete choice names to 100 characters.
+pub const MAX_CHOICE_NAME_LENGTH: usize = 100;
+// Keeps the 25 entries of /config_history under discord's embed description limit.
+pub const MAX_HISTORY_ACTION_LENGTH: usize = 60;
+// Config history entries are attached to their #pacemanbot-audit message as
+// `config_history_<id>.json`, and read back from there on startup.
+pub const CONFIG_HISTORY_FILE_PREFIX: &str = "config_history_";
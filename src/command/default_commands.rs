@@ -1,9 +1,17 @@
+#[path = "config_history.rs"]
+mod config_history;
+#[path = "config_revert.rs"]
+mod config_revert;
+#[path = "preview_embed.rs"]
+mod preview_embed;
 #[path = "remove_pb_roles.rs"]
 mod remove_pb_roles;
 #[path = "remove_roles.rs"]
 mod remove_roles;
 #[path = "send_message.rs"]
 mod send_message;
+#[path = "settings.rs"]
+mod settings;
 #[path = "setup_default_roles.rs"]
 mod setup_default_roles;
 #[path = "setup_pb_roles.rs"]
@@ -12,24 +20,40 @@ mod setup_pb_roles;
 mod setup_pings;
 #[path = "setup_roles.rs"]
 mod setup_roles;
+#[path = "subscribe.rs"]
+mod subscribe;
 #[path = "validate_config.rs"]
 mod validate_config;
 #[path = "whitelist.rs"]
 mod whitelist;
+#[path = "whitelist_export.rs"]
+mod whitelist_export;
+#[path = "whitelist_import.rs"]
+mod whitelist_import;
+#[path = "whitelist_migrate.rs"]
+mod whitelist_migrate;
 #[path = "whitelist_uuid.rs"]
 mod whitelist_uuid;
 
 use crate::command::Command;
 
+use config_history::CONFIG_HISTORY;
+use config_revert::CONFIG_REVERT;
+use preview_embed::PREVIEW_EMBED;
 use remove_pb_roles::REMOVE_PB_ROLES;
 use remove_roles::REMOVE_ROLES;
 use send_message::SEND_MESSAGE;
+use settings::SETTINGS;
 use setup_default_roles::SETUP_DEFAULT_ROLES;
 use setup_pb_roles::SETUP_PB_ROLES;
 use setup_pings::SETUP_PINGS;
 use setup_roles::SETUP_ROLES;
+use subscribe::SUBSCRIBE;
 use validate_config::VALIDATE_CONFIG;
 use whitelist::WHITELIST;
+use whitelist_export::WHITELIST_EXPORT;
+use whitelist_import::WHITELIST_IMPORT;
+use whitelist_migrate::WHITELIST_MIGRATE;
 use whitelist_uuid::WHITELIST_UUID;
 
 pub fn get_default_commands() -> Vec<&'static dyn Command> {
@@ -41,8 +65,16 @@ pub fn get_default_commands() -> Vec<&'static dyn Command> {
         &SETUP_PB_ROLES,
         &WHITELIST,
         &WHITELIST_UUID,
+        &WHITELIST_IMPORT,
+        &WHITELIST_EXPORT,
+        &WHITELIST_MIGRATE,
         &VALIDATE_CONFIG,
         &REMOVE_ROLES,
         &REMOVE_PB_ROLES,
+        &SETTINGS,
+        &SUBSCRIBE,
+        &PREVIEW_EMBED,
+        &CONFIG_HISTORY,
+        &CONFIG_REVERT,
     ];
 }
@@ -1,9 +1,18 @@
+mod audit;
 mod command;
 mod consts;
 mod default_commands;
+mod options;
+mod registration;
 mod utils;
 
-pub use command::{Command, CommandContext};
+pub use audit::{
+    format_options, get_config_snapshot, is_internal_setting, load_config_history,
+    record_config_change,
+};
+pub use command::{AutocompleteContext, Command, CommandContext};
 pub use consts::*;
 pub use default_commands::*;
+pub use options::{CommandOptions, FromOptions};
+pub use registration::{register_commands, remove_guild_commands, CommandScope};
 pub use utils::*;
@@ -0,0 +1,127 @@
+use std::error::Error;
+
+use serenity::model::{
+    channel::Attachment,
+    prelude::{
+        application_command::{CommandDataOption, CommandDataOptionValue},
+        command::CommandOptionType,
+    },
+};
+
+// Typed view over the options of a command interaction, so that commands don't
+// need to match on the raw json values themselves.
+#[derive(Clone, Copy)]
+pub struct CommandOptions<'a> {
+    options: &'a [CommandDataOption],
+}
+
+pub struct Subcommand<'a> {
+    // None unless the subcommand is nested in a subcommand group.
+    #[allow(dead_code)]
+    pub group: Option<&'a str>,
+    pub name: &'a str,
+    pub options: CommandOptions<'a>,
+}
+
+// Implemented by structs that hold the parsed options of a command.
+pub trait FromOptions: Sized {
+    fn from_options(options: CommandOptions<'_>) -> Result<Self, Box<dyn Error>>;
+}
+
+impl<'a> CommandOptions<'a> {
+    pub fn new(options: &'a [CommandDataOption]) -> Self {
+        Self { options }
+    }
+
+    pub fn parse<T: FromOptions>(self) -> Result<T, Box<dyn Error>> {
+        T::from_options(self)
+    }
+
+    fn find(&self, name: &str) -> Option<&'a CommandDataOption> {
+        self.options.iter().find(|option| option.name == name)
+    }
+
+    pub fn string(&self, name: &str) -> Result<Option<String>, Box<dyn Error>> {
+        match self.find(name).and_then(|option| option.value.as_ref()) {
+            Some(value) => match value.as_str() {
+                Some(str) => Ok(Some(str.to_owned())),
+                None => Err(format!("failed to parse string for {} option.", name).into()),
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn required_string(&self, name: &str) -> Result<String, Box<dyn Error>> {
+        match self.string(name)? {
+            Some(value) => Ok(value),
+            None => Err(format!("failed to get value for {} option.", name).into()),
+        }
+    }
+
+    pub fn integer(&self, name: &str) -> Result<Option<i64>, Box<dyn Error>> {
+        match self.find(name).and_then(|option| option.value.as_ref()) {
+            Some(value) => match value.as_i64() {
+                Some(int) => Ok(Some(int)),
+                None => Err(format!("failed to parse integer for {} option.", name).into()),
+            },
+            None => Ok(None),
+        }
+    }
+
+    // Split thresholds are stored as whole minutes.
+    pub fn minutes(&self, name: &str) -> Result<Option<u8>, Box<dyn Error>> {
+        match self.integer(name)? {
+            Some(int) => match u8::try_from(int) {
+                Ok(minutes) => Ok(Some(minutes)),
+                Err(_) => {
+                    Err(format!("invalid number of minutes for {} option: {}.", name, int).into())
+                }
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn boolean(&self, name: &str) -> Result<Option<bool>, Box<dyn Error>> {
+        match self.find(name).and_then(|option| option.value.as_ref()) {
+            Some(value) => match value.as_bool() {
+                Some(bool) => Ok(Some(bool)),
+                None => Err(format!("failed to parse boolean for {} option.", name).into()),
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn attachment(&self, name: &str) -> Result<Option<Attachment>, Box<dyn Error>> {
+        match self.find(name).and_then(|option| option.resolved.as_ref()) {
+            Some(CommandDataOptionValue::Attachment(attachment)) => Ok(Some(attachment.to_owned())),
+            Some(_) => Err(format!("failed to parse attachment for {} option.", name).into()),
+            None => Ok(None),
+        }
+    }
+
+    // Returns the invoked subcommand, looking through a subcommand group if there is one.
+    pub fn subcommand(&self) -> Result<Subcommand<'a>, Box<dyn Error>> {
+        let option = match self.options.first() {
+            Some(option) => option,
+            None => return Err(String::from("failed to get subcommand.").into()),
+        };
+        match option.kind {
+            CommandOptionType::SubCommand => Ok(Subcommand {
+                group: None,
+                name: option.name.as_str(),
+                options: CommandOptions::new(&option.options),
+            }),
+            CommandOptionType::SubCommandGroup => match option.options.first() {
+                Some(subcommand) if subcommand.kind == CommandOptionType::SubCommand => {
+                    Ok(Subcommand {
+                        group: Some(option.name.as_str()),
+                        name: subcommand.name.as_str(),
+                        options: CommandOptions::new(&subcommand.options),
+                    })
+                }
+                _ => Err(format!("failed to get subcommand of group: '{}'.", option.name).into()),
+            },
+            _ => Err(format!("option: '{}' is not a subcommand.", option.name).into()),
+        }
+    }
+}
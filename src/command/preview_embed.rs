@@ -0,0 +1,137 @@
+use std::error::Error;
+
+use serenity::{
+    async_trait,
+    builder::{CreateApplicationCommand, CreateEmbedAuthor},
+    model::prelude::command::CommandOptionType,
+};
+
+use crate::{
+    cache::{Split, Structure},
+    command::{get_guild_settings, Command, CommandContext, CommandOptions},
+    dispatcher::{
+        current_timestamp, format_time, mins_secs_to_millis, pace_quality_color, PaceEmbed,
+        CREDITS_EMOJI, PEARL_EMOJI, ROD_EMOJI, STATS_URL_PREFIX,
+    },
+    ws::{ItemData, LiveAccount},
+};
+
+pub struct PreviewEmbed;
+
+#[async_trait]
+impl Command for PreviewEmbed {
+    fn name(&self) -> &str {
+        "preview_embed"
+    }
+
+    fn description(&self) -> &str {
+        "Preview a pace message with the embed settings of this server."
+    }
+
+    fn changes_config(&self, _options: CommandOptions<'_>) -> bool {
+        false
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.create_option(|option| {
+            option
+                .name("split")
+                .description("Split to preview, defaults to Structure 2.")
+                .kind(CommandOptionType::String)
+                .add_string_choice("First Structure", Split::FirstStructure.to_str())
+                .add_string_choice("Second Structure", Split::SecondStructure.to_str())
+                .add_string_choice("Blind", Split::Blind.to_str())
+                .add_string_choice("Eye Spy", Split::EyeSpy.to_str())
+                .add_string_choice("End Enter", Split::EndEnter.to_str())
+                .add_string_choice("Finish", "F")
+        })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+
+        let split = match context.options().string("split")? {
+            Some(split) => Split::from_str(split.as_str()),
+            None => Some(Split::SecondStructure),
+        };
+        let settings = get_guild_settings(ctx, &context.guild_id).await?;
+        let template = settings.embed_template.to_owned();
+        let locale = settings.locale;
+
+        let split_millis = mins_secs_to_millis(match split.as_ref() {
+            Some(Split::FirstStructure) => (2, 10),
+            Some(Split::SecondStructure) => (4, 5),
+            Some(Split::Blind) => (5, 20),
+            Some(Split::EyeSpy) => (6, 45),
+            Some(Split::EndEnter) => (7, 30),
+            None => (9, 15),
+        });
+        let split_time = format_time(split_millis);
+        let pace_msg = match split.as_ref() {
+            Some(split) => {
+                let structure = Some(Structure::Bastion);
+                format!(
+                    "{}  {} - {}",
+                    split.get_emoji(&structure).unwrap_or_default(),
+                    split_time,
+                    locale.tr(split.desc(&structure).unwrap_or_default().as_str())
+                )
+            }
+            None => format!(
+                "{}  {} - {}",
+                CREDITS_EMOJI,
+                split_time,
+                locale.tr("Finish")
+            ),
+        };
+        let mut items_msg = String::new();
+        ItemData::format_item_count(&mut items_msg, ROD_EMOJI, "7".to_string());
+        ItemData::format_item_count(&mut items_msg, PEARL_EMOJI, "12".to_string());
+        let mut author = CreateEmbedAuthor::default();
+        author.name("Sample Runner");
+        let pace_embed = PaceEmbed {
+            author,
+            split: split.to_owned(),
+            pace_msg,
+            items_msg,
+            live_account: settings.live_platforms.first().map(|platform| LiveAccount {
+                platform: platform.to_owned(),
+                name: "paceman".to_string(),
+            }),
+            stats_link: format!("{}0", STATS_URL_PREFIX),
+            last_updated: current_timestamp() as i64 * 1000,
+            is_bastionless: false,
+            // Halfway between the thresholds, to show the blended colour.
+            pace_color: pace_quality_color(
+                split_millis,
+                &[split_millis - 20000, split_millis + 20000],
+            ),
+            projection_msg: split.as_ref().map(|_| "9:12 ± 0:40".to_string()),
+            warning_msg: None,
+            locale,
+        };
+
+        let response_content = locale.trf("Preview of a {0} pace message:", &[split_time.as_str()]);
+        command
+            .edit_original_interaction_response(&ctx.http, |m| {
+                if template.compact {
+                    m.content(format!(
+                        "{}\n{}",
+                        response_content,
+                        pace_embed.render_compact(&template)
+                    ))
+                } else {
+                    m.content(response_content)
+                        .set_embed(pace_embed.render(&template))
+                }
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub const PREVIEW_EMBED: PreviewEmbed = PreviewEmbed {};
@@ -0,0 +1,231 @@
+use std::error::Error;
+
+use serde_json::{Map, Value};
+use serenity::{
+    builder::CreateApplicationCommand,
+    client::Context,
+    model::{application::command::Command as ApplicationCommand, id::GuildId},
+};
+
+use crate::{
+    command::{get_default_commands, Command},
+    locale::LOCALES,
+};
+
+// Keys of a command that are compared to decide whether it needs to be uploaded again.
+const COMPARED_KEYS: [&str; 7] = [
+    "name",
+    "description",
+    "name_localizations",
+    "description_localizations",
+    "options",
+    "default_member_permissions",
+    "dm_permission",
+];
+
+#[derive(Clone, Copy)]
+pub enum CommandScope {
+    Global,
+    // Only used for development servers.
+    Guild(GuildId),
+}
+
+#[derive(Default)]
+pub struct RegistrationSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+}
+
+impl RegistrationSummary {
+    pub fn to_str(&self) -> String {
+        format!(
+            "{} created, {} updated, {} deleted, {} unchanged",
+            self.created, self.updated, self.deleted, self.unchanged
+        )
+    }
+}
+
+fn create_command(command: &dyn Command, scope: CommandScope) -> CreateApplicationCommand {
+    let mut c = CreateApplicationCommand::default();
+    c.name(command.name()).description(command.description());
+    // dm_permission is ignored for guild commands, and Discord doesn't return it for them.
+    if let CommandScope::Global = scope {
+        c.dm_permission(false);
+    }
+    for locale in LOCALES {
+        if locale.has_translation(command.name()) {
+            c.name_localized(locale.to_str(), locale.tr(command.name()));
+        }
+        if locale.has_translation(command.description()) {
+            c.description_localized(locale.to_str(), locale.tr(command.description()));
+        }
+    }
+    command.create_options(&mut c);
+    // Edits only change the keys that are sent, so removed permissions and
+    // options have to be sent explicitly.
+    match command.default_member_permissions() {
+        Some(permissions) => {
+            c.default_member_permissions(permissions);
+        }
+        None => {
+            c.0.insert("default_member_permissions", Value::Null);
+        }
+    }
+    c.0.entry("options").or_insert(Value::Array(vec![]));
+    c
+}
+
+// Drops the values that Discord treats as defaults, so that a new command and
+// the one returned by Discord compare equal when nothing changed.
+fn normalize(value: Value) -> Option<Value> {
+    match value {
+        Value::Null | Value::Bool(false) => None,
+        Value::Array(values) => {
+            let values: Vec<Value> = values
+                .into_iter()
+                .map(|value| normalize(value).unwrap_or(Value::Null))
+                .collect();
+            if values.is_empty() {
+                None
+            } else {
+                Some(Value::Array(values))
+            }
+        }
+        Value::Object(map) => {
+            let map: Map<String, Value> = map
+                .into_iter()
+                .filter_map(|(key, value)| normalize(value).map(|value| (key, value)))
+                .collect();
+            if map.is_empty() {
+                None
+            } else {
+                Some(Value::Object(map))
+            }
+        }
+        value => Some(value),
+    }
+}
+
+fn is_changed(existing: &ApplicationCommand, new: &CreateApplicationCommand) -> bool {
+    let existing = match serde_json::to_value(existing) {
+        Ok(Value::Object(existing)) => existing,
+        _ => return true,
+    };
+    COMPARED_KEYS.iter().any(|key| {
+        normalize(existing.get(*key).cloned().unwrap_or(Value::Null))
+            != normalize(new.0.get(key).cloned().unwrap_or(Value::Null))
+    })
+}
+
+// Registers the default commands in the scope, only uploading the commands
+// that are new or changed and deleting the ones that no longer exist.
+pub async fn register_commands(
+    ctx: &Context,
+    scope: CommandScope,
+) -> Result<RegistrationSummary, Box<dyn Error>> {
+    let existing_commands = match scope {
+        CommandScope::Global => {
+            ApplicationCommand::get_global_application_commands_with_localizations(&ctx.http)
+                .await?
+        }
+        CommandScope::Guild(guild_id) => {
+            guild_id
+                .get_application_commands_with_localizations(&ctx.http)
+                .await?
+        }
+    };
+    let commands = get_default_commands();
+    let mut summary = RegistrationSummary::default();
+
+    for command in commands.iter() {
+        let new_command = create_command(*command, scope);
+        match existing_commands
+            .iter()
+            .find(|existing| existing.name == command.name())
+        {
+            Some(existing) if !is_changed(existing, &new_command) => summary.unchanged += 1,
+            Some(existing) => {
+                match scope {
+                    CommandScope::Global => {
+                        ApplicationCommand::edit_global_application_command(
+                            &ctx.http,
+                            existing.id,
+                            |c| {
+                                *c = new_command;
+                                c
+                            },
+                        )
+                        .await?;
+                    }
+                    CommandScope::Guild(guild_id) => {
+                        guild_id
+                            .edit_application_command(&ctx.http, existing.id, |c| {
+                                *c = new_command;
+                                c
+                            })
+                            .await?;
+                    }
+                };
+                summary.updated += 1;
+            }
+            None => {
+                match scope {
+                    CommandScope::Global => {
+                        ApplicationCommand::create_global_application_command(&ctx.http, |c| {
+                            *c = new_command;
+                            c
+                        })
+                        .await?;
+                    }
+                    CommandScope::Guild(guild_id) => {
+                        guild_id
+                            .create_application_command(&ctx.http, |c| {
+                                *c = new_command;
+                                c
+                            })
+                            .await?;
+                    }
+                };
+                summary.created += 1;
+            }
+        }
+    }
+
+    for existing in existing_commands.iter().filter(|existing| {
+        !commands
+            .iter()
+            .any(|command| command.name() == existing.name)
+    }) {
+        match scope {
+            CommandScope::Global => {
+                ApplicationCommand::delete_global_application_command(&ctx.http, existing.id)
+                    .await?
+            }
+            CommandScope::Guild(guild_id) => {
+                guild_id
+                    .delete_application_command(&ctx.http, existing.id)
+                    .await?
+            }
+        };
+        summary.deleted += 1;
+    }
+    Ok(summary)
+}
+
+// Removes the commands that older versions registered per guild, so that they
+// don't show up twice next to the global ones.
+pub async fn remove_guild_commands(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Result<usize, Box<dyn Error>> {
+    let existing_commands = guild_id.get_application_commands(&ctx.http).await?;
+    if existing_commands.is_empty() {
+        return Ok(0);
+    }
+    guild_id
+        .set_application_commands(&ctx.http, |commands| commands)
+        .await?;
+    Ok(existing_commands.len())
+}
@@ -44,23 +44,7 @@ impl Command for RemoveRoles {
     async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
         let ctx = context.ctx;
         let command = context.interaction;
-        let split_name = match command.data.options.get(0) {
-            Some(option) => match option.name.as_str() {
-                "split_name" => match option.value.to_owned() {
-                    Some(value) => match value.as_str() {
-                        Some(str) => str.to_owned(),
-                        None => return Err("failed to convert 'split_name' into '&str'.".into()),
-                    },
-                    None => return Err("failed to get value for option name: 'split_name'.".into()),
-                },
-                _ => {
-                    return Err("unrecognized option name.".into());
-                }
-            },
-            None => {
-                return Err("no options provided.".into());
-            }
-        };
+        let split_name = context.options().required_string("split_name")?;
         let split = match Split::from_command_param(&split_name) {
             Some(split) => split,
             None => return Err(format!("unrecognized split name: '{}'.", split_name).into()),
@@ -1,19 +1,14 @@
-use std::{cmp::Ordering, error::Error};
+use std::error::Error;
 
-use serenity::{
-    async_trait,
-    builder::{CreateActionRow, CreateApplicationCommand},
-    model::prelude::component::ButtonStyle,
-};
+use serenity::{async_trait, builder::CreateApplicationCommand};
 
 use crate::{
-    cache::Split,
-    command::{create_select_option, Command, CommandContext},
-    config::{
-        extract_split_from_pb_role_name, extract_split_from_role_name, ROLE_PREFIX,
-        ROLE_PREFIX_115, ROLE_PREFIX_17, ROLE_PREFIX_AA,
+    cache::RoleMessage,
+    command::{
+        create_role_selection_rows, get_guild_locale, get_guild_settings, get_role_selection_roles,
+        update_guild_settings, update_role_message, Command, CommandContext,
     },
-    dispatcher::mins_secs_to_millis,
+    config::PACEMANBOT_SETTINGS_CHANNEL,
 };
 
 pub struct SendMessage;
@@ -38,184 +33,16 @@ impl Command for SendMessage {
     async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
         let ctx = context.ctx;
         let command = context.interaction;
-        let roles = context.guild_id.roles(&ctx.http).await?;
-        let mut errors = Vec::new();
-
-        let mut roles = roles
-            .iter()
-            .map(|(_, role)| role)
-            .filter(|r| {
-                r.name.starts_with(ROLE_PREFIX)
-                    && !r.name.starts_with(ROLE_PREFIX_115)
-                    && !r.name.starts_with(ROLE_PREFIX_17)
-                    && !r.name.starts_with(ROLE_PREFIX_AA)
-            })
-            .collect::<Vec<_>>();
-        roles.sort_by(|r1, r2| {
-            let r1_order;
-            let r2_order;
-            if r1.name.contains("PB") {
-                r1_order = 0;
-            } else {
-                let (_, minutes, seconds) = match extract_split_from_role_name(&r1.name) {
-                    Ok(tup) => tup,
-                    Err(err) => {
-                        errors.push(format!(
-                            "failed to get split from role name: '{}': {}",
-                            r1.name, err
-                        ));
-                        return Ordering::Equal;
-                    }
-                };
-                r1_order = mins_secs_to_millis((minutes, seconds));
-            }
-            if r2.name.contains("PB") {
-                r2_order = 0;
-            } else {
-                let (_, minutes, seconds) = match extract_split_from_role_name(&r2.name) {
-                    Ok(tup) => tup,
-                    Err(err) => {
-                        errors.push(format!(
-                            "failed to get split from role name: '{}': {}",
-                            r2.name, err
-                        ));
-                        return Ordering::Equal;
-                    }
-                };
-                r2_order = mins_secs_to_millis((minutes, seconds));
-            }
-            r1_order.cmp(&r2_order)
-        });
-        let mut select_bastion_role_action_row = CreateActionRow::default();
-        let mut select_fortress_role_action_row = CreateActionRow::default();
-        let mut select_blind_role_action_row = CreateActionRow::default();
-        let mut select_eye_spy_role_action_row = CreateActionRow::default();
-        let mut select_end_enter_role_action_row = CreateActionRow::default();
-
-        let send_bastion_picker = roles.iter().any(|role| {
-            if role.name.contains("PB") {
-                let split = match extract_split_from_pb_role_name(&role.name) {
-                    Some(split) => split,
-                    None => {
-                        errors.push(format!(
-                            "failed to get pb split from role name: '{}'.",
-                            role.name
-                        ));
-                        return false;
-                    }
-                };
-                return split == Split::FirstStructure;
-            }
-            let (split, _minutes, _seconds) = match extract_split_from_role_name(&role.name) {
-                Ok(tup) => tup,
-                Err(err) => {
-                    errors.push(format!(
-                        "failed to get split from role name: '{}': {}",
-                        role.name, err
-                    ));
-                    return false;
-                }
-            };
-            split == Split::FirstStructure
-        });
-
-        select_bastion_role_action_row.create_select_menu(|m| {
-            m.custom_id("select_structure1_role")
-                .placeholder("Choose a First Structure Role...")
-                .options(|o| {
-                    match create_select_option(o, &roles, Split::FirstStructure) {
-                        Ok(_) => (),
-                        Err(err) => {
-                            errors.push(format!("{}", err));
-                        }
-                    }
-                    o
-                })
-        });
-        select_fortress_role_action_row.create_select_menu(|m| {
-            m.custom_id("select_structure2_role")
-                .placeholder("Choose a Second Structure Role...")
-                .options(|o| {
-                    match create_select_option(o, &roles, Split::SecondStructure) {
-                        Ok(_) => (),
-                        Err(err) => {
-                            errors.push(format!("{}", err));
-                        }
-                    }
-                    o
-                })
-        });
-        select_blind_role_action_row.create_select_menu(|m| {
-            m.custom_id("select_blind_role")
-                .placeholder("Choose a Blind Role...")
-                .options(|o| {
-                    match create_select_option(o, &roles, Split::Blind) {
-                        Ok(_) => (),
-                        Err(err) => {
-                            errors.push(format!("{}", err));
-                        }
-                    }
-                    o
-                })
-        });
-        select_eye_spy_role_action_row.create_select_menu(|m| {
-            m.custom_id("select_eye_spy_role")
-                .placeholder("Choose an Eye Spy Role...")
-                .options(|o| {
-                    match create_select_option(o, &roles, Split::EyeSpy) {
-                        Ok(_) => (),
-                        Err(err) => {
-                            errors.push(format!("{}", err));
-                        }
-                    }
-                    o
-                })
-        });
-        select_end_enter_role_action_row.create_select_menu(|m| {
-            m.custom_id("select_end_enter_role")
-                .placeholder("Choose an End Enter Role...")
-                .options(|o| {
-                    match create_select_option(o, &roles, Split::EndEnter) {
-                        Ok(_) => (),
-                        Err(err) => {
-                            errors.push(format!("{}", err));
-                        }
-                    }
-                    o
-                })
-        });
-        let mut remove_roles_action_row = CreateActionRow::default();
-
-        remove_roles_action_row.create_button(|c| {
-            c.style(ButtonStyle::Primary)
-                .label("Remove ALL PMB Roles")
-                .custom_id("remove_pmb_roles")
-        });
-
-        let content = "Select roles based on the splits and paces you wish to follow.";
+        let (roles, mut errors) = get_role_selection_roles(ctx, &context.guild_id).await?;
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        let rows = create_role_selection_rows(&roles, locale, &mut errors);
 
-        match command
-            .channel_id
-            .send_message(&ctx.http, |data| {
-                data.content(content).components(|c| {
-                    if send_bastion_picker {
-                        c.add_action_row(select_bastion_role_action_row)
-                            .add_action_row(select_fortress_role_action_row)
-                            .add_action_row(select_blind_role_action_row)
-                            .add_action_row(select_eye_spy_role_action_row)
-                            .add_action_row(select_end_enter_role_action_row)
-                    } else {
-                        c.add_action_row(select_fortress_role_action_row)
-                            .add_action_row(select_blind_role_action_row)
-                            .add_action_row(select_eye_spy_role_action_row)
-                            .add_action_row(select_end_enter_role_action_row)
-                            .add_action_row(remove_roles_action_row.to_owned())
-                    }
-                })
-            })
-            .await
-        {
-            Ok(_) => (),
+        let role_message = RoleMessage {
+            channel_id: command.channel_id,
+            message_ids: Vec::new(),
+        };
+        let role_message = match update_role_message(ctx, &role_message, &rows, locale).await {
+            Ok(role_message) => role_message,
             Err(err) => {
                 let mut content = format!("failed to send role selection message: {}", err);
                 if !errors.is_empty() {
@@ -224,27 +51,29 @@ impl Command for SendMessage {
                 return Err(content.into());
             }
         };
-        if send_bastion_picker {
-            match command
-                .channel_id
-                .send_message(&ctx.http, |m| {
-                    m.content("")
-                        .components(|c| c.add_action_row(remove_roles_action_row))
-                })
-                .await
-            {
-                Ok(_) => (),
-                Err(err) => {
-                    let mut content = format!("failed to send bastion message: {}", err);
-                    if !errors.is_empty() {
-                        content = format!("{}\n\t{}", content, errors.join("\n\t"));
-                    }
-                    return Err(content.into());
-                }
-            };
-        }
+
+        // Remembered in the settings, so that the message is refreshed when the pace-roles change.
+        let settings = get_guild_settings(ctx, &context.guild_id).await.ok();
+        let response_content = match settings {
+            Some(mut settings) => {
+                settings.role_messages.push(role_message);
+                let value = settings.get("role_messages").unwrap_or_default();
+                update_guild_settings(
+                    ctx,
+                    &context.guild_id,
+                    "role_messages",
+                    Some(value.as_str()),
+                )
+                .await?;
+                locale.tr("Sent message!")
+            }
+            None => locale.trf(
+                "Sent message! Create #{0} to have it refreshed when the pace-roles change.",
+                &[PACEMANBOT_SETTINGS_CHANNEL],
+            ),
+        };
         match command
-            .edit_original_interaction_response(&ctx.http, |m| m.content("Sent message!"))
+            .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
             .await
         {
             Ok(_) => (),
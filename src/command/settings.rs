@@ -0,0 +1,92 @@
+use std::error::Error;
+
+use serenity::{
+    async_trait, builder::CreateApplicationCommand, model::prelude::command::CommandOptionType,
+};
+
+use crate::{
+    cache::SETTING_KEYS,
+    command::{update_guild_settings, Command, CommandContext},
+};
+
+pub struct Settings;
+
+#[async_trait]
+impl Command for Settings {
+    fn name(&self) -> &str {
+        "settings"
+    }
+
+    fn description(&self) -> &str {
+        "Change or reset a bot setting for the server."
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'set' or 'reset'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Set", "set")
+                    .add_string_choice("Reset", "reset")
+            })
+            .create_option(|option| {
+                option
+                    .name("key")
+                    .description("Name of the setting that you want to change.")
+                    .required(true)
+                    .kind(CommandOptionType::String);
+                for key in SETTING_KEYS {
+                    option.add_string_choice(key, key);
+                }
+                option
+            })
+            .create_option(|option| {
+                option
+                    .name("value")
+                    .description("New value for the setting(required for 'set').")
+                    .kind(CommandOptionType::String)
+            })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+
+        let options = context.options();
+        let action = options.required_string("action")?;
+        let key = options.required_string("key")?;
+        let value = options.string("value")?;
+
+        if !SETTING_KEYS.contains(&key.as_str()) {
+            return Err(format!("unrecognized setting: '{}'.", key).into());
+        }
+        let value = match action.as_str() {
+            "set" => match value {
+                Some(value) => Some(value),
+                None => return Err("parameter 'value' is undefined for 'set'.".into()),
+            },
+            _ => None,
+        };
+        let settings =
+            update_guild_settings(ctx, &context.guild_id, key.as_str(), value.as_deref()).await?;
+
+        command
+            .edit_original_interaction_response(&ctx.http, |m| {
+                m.content(format!(
+                    "Setting '{}' is now: '{}'.",
+                    key,
+                    settings.get(key.as_str()).unwrap_or_default()
+                ))
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub const SETTINGS: Settings = Settings {};
@@ -6,7 +6,10 @@ use serenity::{
 
 use crate::{
     cache::{GuildCacheEntry, Split},
-    command::{create_guild_role, remove_runner_pings, Command, CommandContext},
+    command::{
+        autocomplete_options, create_guild_role, remove_runner_pings, AutocompleteContext, Command,
+        CommandContext, CommandOptions, FromOptions,
+    },
     config::{Config, ROLE_PREFIX, ROLE_PREFIX_115, ROLE_PREFIX_17, ROLE_PREFIX_AA},
 };
 
@@ -40,6 +43,7 @@ impl Command for SetupPings {
                 option
                     .name("ign")
                     .description("In-game name of the runner you want to setup pings for.")
+                    .set_autocomplete(true)
                     .required(true)
                     .kind(CommandOptionType::String)
             })
@@ -59,6 +63,7 @@ impl Command for SetupPings {
                 option
                     .name("time")
                     .description("The time of the split that you want for the runner.")
+                    .set_autocomplete(true)
                     .kind(CommandOptionType::Integer)
             })
     }
@@ -68,95 +73,12 @@ impl Command for SetupPings {
         let guild_id = context.guild_id;
         let command = context.interaction;
 
-        let mut action = String::new();
-        let mut ign = String::new();
-        let mut split = String::new();
-        let mut time = 0;
-        for option in command.data.options.iter() {
-            match option.name.as_str() {
-                "action" => {
-                    action = match option.value.to_owned() {
-                        Some(value) => match value.as_str() {
-                            Some(str) => str.to_owned(),
-                            None => {
-                                return Err(
-                                    "failed to setup pings: convert 'action' value to string."
-                                        .into(),
-                                )
-                            }
-                        },
-                        None => {
-                            return Err(
-                                "failed to setup pings: get value for 'action' for command".into()
-                            )
-                        }
-                    }
-                }
-                "ign" => {
-                    ign = match option.value.to_owned() {
-                        Some(value) => match value.as_str() {
-                            Some(str) => str.to_owned(),
-                            None => {
-                                return Err(
-                                    "failed to setup pings: convert 'ign' value to string.".into()
-                                )
-                            }
-                        },
-                        None => {
-                            return Err(
-                                "failed to setup pings: get value for 'ign' for command".into()
-                            )
-                        }
-                    }
-                }
-                "split" => {
-                    split = match option.value.to_owned() {
-                        Some(value) => match value.as_str() {
-                            Some(str) => str.to_owned(),
-                            None => {
-                                return Err(
-                                    "failed to setup pings: convert 'split' value to string."
-                                        .into(),
-                                )
-                            }
-                        },
-                        None => {
-                            return Err(
-                                "failed to setup pings: get value for 'split' for command.".into()
-                            )
-                        }
-                    }
-                }
-                "time" => {
-                    time = match option.value.to_owned() {
-                        Some(value) => match value.as_u64() {
-                            Some(int) => int as u8,
-                            None => {
-                                return Err(
-                                    "failed to setup pings: convert 'time' value to u64".into()
-                                )
-                            }
-                        },
-                        None => {
-                            return Err(
-                                "failed to setup pings: get value for 'time' for command.".into()
-                            )
-                        }
-                    }
-                }
-                _ => (),
-            }
-        }
-        let split = match Split::from_str(split.as_str()) {
-            Some(split) => split,
-            None => {
-                return Err(format!(
-                    "failed to setup pings: construct Split from str: '{}'.",
-                    split
-                )
-                .into())
-            }
-        };
+        let SetupPingsOptions {
+            action,
+            ign,
+            split,
+            time,
+        } = context.options().parse()?;
         let guild_data = Config::parse_config_for_guild(&ctx, guild_id).await?;
         let is_private = match GuildCacheEntry::is_private(
             guild_data.name.to_string(),
@@ -275,6 +197,39 @@ impl Command for SetupPings {
         }
         Ok(())
     }
+
+    async fn autocomplete(&self, context: AutocompleteContext<'_>) -> Result<(), Box<dyn Error>> {
+        autocomplete_options(&context).await
+    }
+}
+
+struct SetupPingsOptions {
+    action: String,
+    ign: String,
+    split: Split,
+    time: u8,
+}
+
+impl FromOptions for SetupPingsOptions {
+    fn from_options(options: CommandOptions<'_>) -> Result<Self, Box<dyn Error>> {
+        let split = options.required_string("split")?;
+        let split = match Split::from_str(split.as_str()) {
+            Some(split) => split,
+            None => {
+                return Err(format!(
+                    "failed to setup pings: construct Split from str: '{}'.",
+                    split
+                )
+                .into())
+            }
+        };
+        Ok(Self {
+            action: options.required_string("action")?,
+            ign: options.required_string("ign")?,
+            split,
+            time: options.minutes("time")?.unwrap_or_default(),
+        })
+    }
 }
 
 pub const SETUP_PINGS: SetupPings = SetupPings {};
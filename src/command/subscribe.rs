@@ -0,0 +1,205 @@
+use std::error::Error;
+
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    model::{permissions::Permissions, prelude::command::CommandOptionType},
+};
+
+use crate::{
+    cache::{Split, SubscriptionCacheEntry, SubscriptionDelivery},
+    command::{
+        autocomplete_options, get_config_lines, get_config_message, get_guild_locale,
+        set_config_message_contents, AutocompleteContext, Command, CommandContext, CommandOptions,
+    },
+    config::{
+        extract_subscription_from_config_line, parse_quiet_hours, parse_time_threshold,
+        PACEMANBOT_SUBSCRIPTIONS_CHANNEL,
+    },
+};
+
+pub struct Subscribe;
+
+#[async_trait]
+impl Command for Subscribe {
+    fn name(&self) -> &str {
+        "subscribe"
+    }
+
+    fn description(&self) -> &str {
+        "Get mentioned personally when a runner is on pace for a split, without pace-roles."
+    }
+
+    // Members subscribe for themselves, so this isn't limited to admins.
+    fn default_member_permissions(&self) -> Option<Permissions> {
+        None
+    }
+
+    fn changes_config(&self, _options: CommandOptions<'_>) -> bool {
+        false
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+            .create_option(|option| {
+                option
+                    .name("action")
+                    .description("Action to perform out of 'add_or_update' or 'remove'.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Add or Update", "add_or_update")
+                    .add_string_choice("Remove", "remove")
+            })
+            .create_option(|option| {
+                option
+                    .name("ign")
+                    .description("In-game name of the runner you want to follow.")
+                    .set_autocomplete(true)
+                    .required(true)
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("split")
+                    .description("Split of the runner that you want to follow.")
+                    .required(true)
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("First Structure", Split::FirstStructure.to_str())
+                    .add_string_choice("Second Structure", Split::SecondStructure.to_str())
+                    .add_string_choice("Blind", Split::Blind.to_str())
+                    .add_string_choice("Eye Spy", Split::EyeSpy.to_str())
+                    .add_string_choice("End Enter", Split::EndEnter.to_str())
+            })
+            .create_option(|option| {
+                option
+                    .name("time")
+                    .description("Get mentioned when the split is under this time(m:ss).")
+                    .set_autocomplete(true)
+                    .kind(CommandOptionType::String)
+            })
+            .create_option(|option| {
+                option
+                    .name("delivery")
+                    .description(
+                        "Where to get notified, defaults to a mention in the pace channel.",
+                    )
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Mention", SubscriptionDelivery::Mention.to_str())
+                    .add_string_choice("Direct Message", SubscriptionDelivery::Dm.to_str())
+            })
+            .create_option(|option| {
+                option
+                    .name("quiet_hours")
+                    .description(
+                        "Hours in the server's utc_offset during which you are not notified(start-end, e.g. 22-7).",
+                    )
+                    .kind(CommandOptionType::String)
+            })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+
+        let options = context.options();
+        let action = options.required_string("action")?;
+        let ign = options.required_string("ign")?;
+        let split = options.required_string("split")?;
+        let time = options.string("time")?;
+        let delivery = options.string("delivery")?;
+        let quiet_hours = options.string("quiet_hours")?;
+        let split = match Split::from_str(split.as_str()) {
+            Some(split) => split,
+            None => {
+                return Err(format!(
+                    "failed to subscribe: construct Split from str: '{}'.",
+                    split
+                )
+                .into())
+            }
+        };
+        let user_id = command.user.id;
+
+        let (channel, message) =
+            get_config_message(ctx, &context.guild_id, PACEMANBOT_SUBSCRIPTIONS_CHANNEL).await?;
+        let mut subscriptions = Vec::new();
+        for line in get_config_lines(&message) {
+            subscriptions.push(extract_subscription_from_config_line(line.as_str())?);
+        }
+        subscriptions.retain(|subscription: &SubscriptionCacheEntry| {
+            subscription.user_id != user_id
+                || subscription.split != split
+                || subscription.runner.to_lowercase() != ign.to_lowercase()
+        });
+        if action == "add_or_update" {
+            let (minutes, seconds) =
+                match time {
+                    Some(time) => parse_time_threshold(time.as_str())?,
+                    None => return Err(
+                        "failed to subscribe: Parameter 'time' is undefined for 'add_or_update'."
+                            .into(),
+                    ),
+                };
+            let delivery = match delivery {
+                Some(delivery) => match SubscriptionDelivery::from_str(delivery.as_str()) {
+                    Some(delivery) => delivery,
+                    None => {
+                        return Err(format!(
+                            "failed to subscribe: unrecognized delivery: '{}'.",
+                            delivery
+                        )
+                        .into())
+                    }
+                },
+                None => SubscriptionDelivery::Mention,
+            };
+            let quiet_hours = match quiet_hours {
+                Some(quiet_hours) => Some(parse_quiet_hours(quiet_hours.as_str())?),
+                None => None,
+            };
+            subscriptions.push(SubscriptionCacheEntry {
+                user_id,
+                runner: ign.to_owned(),
+                split,
+                minutes,
+                seconds,
+                delivery,
+                quiet_hours,
+            });
+        }
+        let new_config = subscriptions
+            .iter()
+            .map(|subscription| subscription.to_config_line())
+            .collect::<Vec<_>>()
+            .join("\n");
+        set_config_message_contents(ctx, &channel, message, new_config).await?;
+
+        let user_subscriptions = subscriptions
+            .iter()
+            .filter(|subscription| subscription.user_id == user_id)
+            .map(|subscription| format!("- {}", subscription.to_formatted_message()))
+            .collect::<Vec<_>>();
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        let response_content = if user_subscriptions.is_empty() {
+            locale.tr("Updated subscriptions! You are not subscribed to any runners.")
+        } else {
+            locale.trf(
+                "Updated subscriptions! You are subscribed to:\n{0}",
+                &[user_subscriptions.join("\n").as_str()],
+            )
+        };
+        command
+            .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+            .await?;
+        Ok(())
+    }
+
+    async fn autocomplete(&self, context: AutocompleteContext<'_>) -> Result<(), Box<dyn Error>> {
+        autocomplete_options(&context).await
+    }
+}
+
+pub const SUBSCRIBE: Subscribe = Subscribe {};
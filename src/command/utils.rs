@@ -1,28 +1,46 @@
-use std::{collections::HashMap, error::Error};
+use std::{collections::HashMap, error::Error, sync::Arc};
 
+use serde_json::Value;
 use serenity::{
-    builder::{CreateSelectMenuOption, CreateSelectMenuOptions},
+    builder::{CreateActionRow, CreateEmbed, CreateSelectMenuOption},
     client::Context,
+    futures::lock::Mutex,
+    http::StatusCode,
     model::{
+        channel::{GuildChannel, Message},
         guild::{Member, Role},
         id::GuildId,
+        prelude::{
+            application_command::CommandDataOption, command::CommandOptionType,
+            component::ButtonStyle,
+        },
     },
+    Error as SerenityError,
 };
 
 use crate::{
-    cache::{PlayerCacheEntry, Split},
-    command::ROLE_COLOR,
+    cache::{Cache, GuildCacheEntry, GuildSettings, PlayerCacheEntry, RoleMessage, Split},
+    command::{
+        AutocompleteContext, MAX_AUTOCOMPLETE_CHOICES, MAX_MESSAGE_ACTION_ROWS,
+        MAX_SELECT_MENU_OPTIONS, MAX_WHITELIST_SEARCH_LENGTH, ROLE_COLOR, SET_PINGS_CUSTOM_ID,
+        WHITELIST_PAGE_CUSTOM_ID_PREFIX, WHITELIST_PAGE_SIZE,
+    },
     config::{
-        extract_split_from_pb_role_name, extract_split_from_role_name, ROLE_PREFIX_115,
-        ROLE_PREFIX_17, ROLE_PREFIX_AA,
+        extract_name_or_uuid_and_splits_from_config_line, extract_setting_from_config_line,
+        extract_split_from_pb_role_name, extract_split_from_role_name, parse_time_threshold,
+        PACEMANBOT_SETTINGS_CHANNEL, ROLE_PREFIX, ROLE_PREFIX_115, ROLE_PREFIX_17, ROLE_PREFIX_AA,
     },
+    dispatcher::{escape_markdown, mins_secs_to_millis},
+    locale::Locale,
+    resolver::is_uuid,
 };
 
-pub fn create_select_option<'a>(
-    o: &'a mut CreateSelectMenuOptions,
-    roles: &Vec<&Role>,
+pub fn create_select_options(
+    roles: &[Role],
     target_split: Split,
-) -> Result<&'a mut CreateSelectMenuOptions, Box<dyn Error>> {
+    locale: Locale,
+) -> Result<Vec<CreateSelectMenuOption>, Box<dyn Error>> {
+    let mut options = Vec::new();
     for role in roles {
         if role.name.contains("PB") {
             let split = match extract_split_from_pb_role_name(&role.name) {
@@ -34,9 +52,12 @@ pub fn create_select_option<'a>(
                 }
             };
             if split == target_split {
-                o.add_option(
+                options.push(
                     CreateSelectMenuOption::default()
-                        .label(format!("PB Pace {}", target_split.alt_desc()))
+                        .label(locale.trf(
+                            "PB Pace {0}",
+                            &[locale.tr(target_split.alt_desc().as_str()).as_str()],
+                        ))
                         .value(role.id.to_string())
                         .to_owned(),
                 );
@@ -44,13 +65,14 @@ pub fn create_select_option<'a>(
         } else {
             let (split, minutes, seconds) = extract_split_from_role_name(&role.name)?;
             if split == target_split {
-                o.add_option(
+                options.push(
                     CreateSelectMenuOption::default()
-                        .label(format!(
-                            "Sub {}:{:02} {}",
-                            minutes,
-                            seconds,
-                            target_split.alt_desc()
+                        .label(locale.trf(
+                            "Sub {0} {1}",
+                            &[
+                                format!("{}:{:02}", minutes, seconds).as_str(),
+                                locale.tr(target_split.alt_desc().as_str()).as_str(),
+                            ],
                         ))
                         .value(role.id.to_string())
                         .to_owned(),
@@ -58,7 +80,247 @@ pub fn create_select_option<'a>(
             }
         }
     }
-    Ok(o)
+    Ok(options)
+}
+
+// Pace-roles shown in the role selection message, PB roles first and then by time.
+pub async fn get_role_selection_roles(
+    ctx: &Context,
+    guild_id: &GuildId,
+) -> Result<(Vec<Role>, Vec<String>), Box<dyn Error>> {
+    let mut errors = Vec::new();
+    let mut roles = guild_id
+        .roles(&ctx.http)
+        .await?
+        .into_values()
+        .filter(|r| {
+            r.name.starts_with(ROLE_PREFIX)
+                && !r.name.starts_with(ROLE_PREFIX_115)
+                && !r.name.starts_with(ROLE_PREFIX_17)
+                && !r.name.starts_with(ROLE_PREFIX_AA)
+        })
+        .collect::<Vec<_>>();
+    let mut role_order = |role: &Role| {
+        if role.name.contains("PB") {
+            return 0;
+        }
+        match extract_split_from_role_name(&role.name) {
+            Ok((_, minutes, seconds)) => mins_secs_to_millis((minutes, seconds)),
+            Err(err) => {
+                errors.push(format!(
+                    "failed to get split from role name: '{}': {}",
+                    role.name, err
+                ));
+                0
+            }
+        }
+    };
+    roles.sort_by_cached_key(|role| role_order(role));
+    Ok((roles, errors))
+}
+
+// One select menu per split, split up into more menus when a split has more
+// pace-roles than fit in one, followed by the button to remove all roles.
+pub fn create_role_selection_rows(
+    roles: &[Role],
+    locale: Locale,
+    errors: &mut Vec<String>,
+) -> Vec<CreateActionRow> {
+    let select_menus = [
+        (
+            Split::FirstStructure,
+            "select_structure1_role",
+            "Choose a First Structure Role...",
+        ),
+        (
+            Split::SecondStructure,
+            "select_structure2_role",
+            "Choose a Second Structure Role...",
+        ),
+        (Split::Blind, "select_blind_role", "Choose a Blind Role..."),
+        (
+            Split::EyeSpy,
+            "select_eye_spy_role",
+            "Choose an Eye Spy Role...",
+        ),
+        (
+            Split::EndEnter,
+            "select_end_enter_role",
+            "Choose an End Enter Role...",
+        ),
+    ];
+    let mut rows = Vec::new();
+    for (split, custom_id, placeholder) in select_menus {
+        let options = match create_select_options(roles, split, locale) {
+            Ok(options) => options,
+            Err(err) => {
+                errors.push(format!("{}", err));
+                continue;
+            }
+        };
+        // A split without pace-roles gets no menu, like first structure usually.
+        let menu_count = options.len().div_ceil(MAX_SELECT_MENU_OPTIONS);
+        for (idx, options) in options.chunks(MAX_SELECT_MENU_OPTIONS).enumerate() {
+            let mut row = CreateActionRow::default();
+            row.create_select_menu(|m| {
+                if idx == 0 {
+                    m.custom_id(custom_id);
+                } else {
+                    m.custom_id(format!("{}:{}", custom_id, idx));
+                }
+                if menu_count > 1 {
+                    m.placeholder(format!(
+                        "{} ({}/{})",
+                        locale.tr(placeholder),
+                        idx + 1,
+                        menu_count
+                    ));
+                } else {
+                    m.placeholder(locale.tr(placeholder));
+                }
+                m.options(|o| o.set_options(options.to_vec()))
+            });
+            rows.push(row);
+        }
+    }
+    let mut remove_roles_action_row = CreateActionRow::default();
+    remove_roles_action_row
+        .create_button(|c| {
+            c.style(ButtonStyle::Primary)
+                .label(locale.tr("Remove ALL PMB Roles"))
+                .custom_id("remove_pmb_roles")
+        })
+        .create_button(|c| {
+            c.style(ButtonStyle::Secondary)
+                .label(locale.tr("My pings"))
+                .custom_id("my_pings")
+        })
+        .create_button(|c| {
+            c.style(ButtonStyle::Secondary)
+                .label(locale.tr("Set all splits"))
+                .custom_id(SET_PINGS_CUSTOM_ID)
+        });
+    rows.push(remove_roles_action_row);
+    rows
+}
+
+// Edits the messages of a role selection message in place, sending more or
+// deleting the extra ones when the number of rows changed. Messages that were
+// deleted are skipped, and none are sent when all of them were deleted.
+pub async fn update_role_message(
+    ctx: &Context,
+    role_message: &RoleMessage,
+    rows: &[CreateActionRow],
+    locale: Locale,
+) -> Result<RoleMessage, SerenityError> {
+    let channel_id = role_message.channel_id;
+    let mut existing_ids = role_message.message_ids.iter();
+    let mut message_ids = Vec::new();
+    for (idx, rows) in rows.chunks(MAX_MESSAGE_ACTION_ROWS).enumerate() {
+        let content = if idx == 0 {
+            locale.tr("Select roles based on the splits and paces you wish to follow.")
+        } else {
+            String::new()
+        };
+        let mut edited_message = None;
+        for message_id in existing_ids.by_ref() {
+            match channel_id
+                .edit_message(&ctx.http, message_id, |m| {
+                    m.content(content.to_owned())
+                        .components(|c| c.set_action_rows(rows.to_vec()))
+                })
+                .await
+            {
+                Ok(message) => {
+                    edited_message = Some(message);
+                    break;
+                }
+                Err(err) if is_not_found(&err) => (),
+                Err(err) => return Err(err),
+            };
+        }
+        let message = match edited_message {
+            Some(message) => message,
+            None if message_ids.is_empty() && !role_message.message_ids.is_empty() => break,
+            None => {
+                channel_id
+                    .send_message(&ctx.http, |m| {
+                        m.content(content)
+                            .components(|c| c.set_action_rows(rows.to_vec()))
+                    })
+                    .await?
+            }
+        };
+        message_ids.push(message.id);
+    }
+    for message_id in existing_ids {
+        match channel_id.delete_message(&ctx.http, message_id).await {
+            Err(err) if !is_not_found(&err) => return Err(err),
+            _ => (),
+        };
+    }
+    Ok(RoleMessage {
+        channel_id,
+        message_ids,
+    })
+}
+
+// Messages or channels that were deleted.
+fn is_not_found(err: &SerenityError) -> bool {
+    match err {
+        SerenityError::Http(err) => err.status_code() == Some(StatusCode::NOT_FOUND),
+        _ => false,
+    }
+}
+
+// Rebuilds the role selection messages remembered in the settings. Deleted
+// messages are forgotten, other failures keep them for the next refresh.
+pub async fn refresh_role_messages(
+    ctx: &Context,
+    guild_id: &GuildId,
+) -> Result<(), Box<dyn Error>> {
+    let has_settings_channel = ctx.cache.guild_channels(guild_id).is_some_and(|channels| {
+        channels
+            .iter()
+            .any(|channel| channel.name == PACEMANBOT_SETTINGS_CHANNEL)
+    });
+    if !has_settings_channel {
+        return Ok(());
+    }
+    let mut settings = get_guild_settings(ctx, guild_id).await?;
+    if settings.role_messages.is_empty() {
+        return Ok(());
+    }
+    let (roles, mut errors) = get_role_selection_roles(ctx, guild_id).await?;
+    let rows = create_role_selection_rows(&roles, settings.locale, &mut errors);
+    let mut role_messages = Vec::new();
+    for role_message in settings.role_messages.iter() {
+        match update_role_message(ctx, role_message, &rows, settings.locale).await {
+            Ok(role_message) if role_message.message_ids.is_empty() => (),
+            Ok(role_message) => role_messages.push(role_message),
+            Err(err) => {
+                role_messages.push(role_message.to_owned());
+                errors.push(format!(
+                    "failed to refresh role message: '{}': {}",
+                    role_message.to_str(),
+                    err
+                ));
+            }
+        }
+    }
+    if role_messages != settings.role_messages {
+        settings.role_messages = role_messages;
+        let value = settings.get("role_messages").unwrap_or_default();
+        update_guild_settings(ctx, guild_id, "role_messages", Some(value.as_str())).await?;
+    }
+    if !errors.is_empty() {
+        return Err(format!(
+            "refreshed role messages with some errors: {}",
+            errors.join("\n\t")
+        )
+        .into());
+    }
+    Ok(())
 }
 
 pub async fn create_guild_role(
@@ -94,15 +356,24 @@ pub fn get_new_config_contents(players: HashMap<String, PlayerCacheEntry>) -> St
         } else {
             "".to_string()
         };
+        let display_name_config = match splits.display_name.as_ref() {
+            Some(display_name) => format!("|{}", display_name),
+            None => "".to_string(),
+        };
+        let runner_config = match splits.uuid.as_ref() {
+            Some(uuid) => format!("{}@{}", key, uuid),
+            None => key.to_string(),
+        };
         let line = format!(
-            "{}:{}/{}/{}/{}/{}{}",
-            key,
+            "{}:{}/{}/{}/{}/{}{}{}",
+            runner_config,
             splits.first_structure,
             splits.second_structure,
             splits.blind,
             splits.eye_spy,
             splits.end_enter,
-            finish_config
+            finish_config,
+            display_name_config
         );
         new_config = format!("{}\n{}", new_config, line);
     }
@@ -132,3 +403,335 @@ pub async fn remove_runner_pings(
     }
     Ok(())
 }
+
+pub async fn get_config_message(
+    ctx: &Context,
+    guild_id: &GuildId,
+    channel_name: &str,
+) -> Result<(GuildChannel, Option<Message>), Box<dyn Error>> {
+    let channels = match ctx.cache.guild_channels(guild_id) {
+        Some(channels) => channels,
+        None => {
+            return Err(format!("failed to get channels for guild id: {}", guild_id).into());
+        }
+    };
+    let channel = match channels.iter().find(|c| c.name == channel_name) {
+        Some(channel) => channel.to_owned(),
+        None => {
+            return Err(
+                format!("failed to find #{} in guild id: {}", channel_name, guild_id).into(),
+            )
+        }
+    };
+    let messages = channel.messages(&ctx.http, |m| m.limit(1)).await?;
+    let message = messages.last().map(|message| message.to_owned());
+    if let Some(message) = message.as_ref() {
+        if !message.author.bot {
+            return Err(format!(
+                "failed as the first message in #{} is not from the bot.",
+                channel_name
+            )
+            .into());
+        }
+    }
+    Ok((channel, message))
+}
+
+pub fn get_config_lines(message: &Option<Message>) -> Vec<String> {
+    match message {
+        Some(message) => message
+            .content
+            .split("\n")
+            .filter(|line| line != &"```" && line != &"")
+            .map(|line| line.to_string())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+pub async fn set_config_message_contents(
+    ctx: &Context,
+    channel: &GuildChannel,
+    message: Option<Message>,
+    contents: String,
+) -> Result<(), Box<dyn Error>> {
+    let new_config = format!("```\n{}\n```", contents);
+    match message {
+        Some(mut message) => {
+            message.edit(&ctx.http, |m| m.content(new_config)).await?;
+        }
+        None => {
+            channel
+                .send_message(&ctx.http, |m| m.content(new_config))
+                .await?;
+        }
+    };
+    Ok(())
+}
+
+pub fn parse_whitelist(
+    message: &Option<Message>,
+) -> Result<HashMap<String, PlayerCacheEntry>, Box<dyn Error>> {
+    let mut players = HashMap::new();
+    for line in get_config_lines(message) {
+        let (name, split_data) = extract_name_or_uuid_and_splits_from_config_line(line.as_str())?;
+        players.insert(name, split_data);
+    }
+    Ok(players)
+}
+
+pub fn parse_guild_settings(message: &Option<Message>) -> Result<GuildSettings, Box<dyn Error>> {
+    let mut settings = GuildSettings::default();
+    for line in get_config_lines(message) {
+        let (key, value) = extract_setting_from_config_line(line.as_str())?;
+        settings.set(key.as_str(), value.as_str())?;
+    }
+    Ok(settings)
+}
+
+pub async fn get_guild_settings(
+    ctx: &Context,
+    guild_id: &GuildId,
+) -> Result<GuildSettings, Box<dyn Error>> {
+    let (_, message) = get_config_message(ctx, guild_id, PACEMANBOT_SETTINGS_CHANNEL).await?;
+    parse_guild_settings(&message)
+}
+
+// Falls back to english if the guild isn't cached, so that replies never fail because of it.
+pub async fn get_guild_locale(cache: &Arc<Mutex<Cache>>, guild_id: &GuildId) -> Locale {
+    match cache.lock().await.entries.get(guild_id) {
+        Some(guild_cache_entry) => guild_cache_entry.settings.locale,
+        None => Locale::En,
+    }
+}
+
+pub async fn update_guild_settings(
+    ctx: &Context,
+    guild_id: &GuildId,
+    key: &str,
+    value: Option<&str>,
+) -> Result<GuildSettings, Box<dyn Error>> {
+    let (channel, message) = get_config_message(ctx, guild_id, PACEMANBOT_SETTINGS_CHANNEL).await?;
+    let mut settings = parse_guild_settings(&message)?;
+    match value {
+        Some(value) => settings.set(key, value)?,
+        None => settings.reset(key)?,
+    };
+    set_config_message_contents(ctx, &channel, message, settings.to_config_contents()).await?;
+    Ok(settings)
+}
+
+pub fn create_whitelist_page(
+    players: &HashMap<String, PlayerCacheEntry>,
+    page: usize,
+    search: &str,
+    locale: Locale,
+) -> (CreateEmbed, CreateActionRow) {
+    let search = search
+        .chars()
+        .take(MAX_WHITELIST_SEARCH_LENGTH)
+        .collect::<String>();
+    let mut runners = players
+        .iter()
+        .filter(|(name, player)| {
+            let search = search.to_lowercase();
+            name.to_lowercase().contains(search.as_str())
+                || player
+                    .display_name
+                    .as_ref()
+                    .map(|display_name| display_name.to_lowercase().contains(search.as_str()))
+                    .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    runners.sort_by_key(|(name, _)| name.to_lowercase());
+    let page_count = runners.len().div_ceil(WHITELIST_PAGE_SIZE).max(1);
+    let page = page.min(page_count - 1);
+
+    let mut description = String::new();
+    for (name, player) in runners
+        .iter()
+        .skip(page * WHITELIST_PAGE_SIZE)
+        .take(WHITELIST_PAGE_SIZE)
+    {
+        let name = match player.display_name.as_ref() {
+            Some(display_name) => format!("{} ({})", name, display_name),
+            None => name.to_string(),
+        };
+        description = format!(
+            "{}**{}**\n{}\n",
+            description,
+            escape_markdown(name.as_str()),
+            player.thresholds_to_str(locale)
+        );
+    }
+    if runners.is_empty() {
+        description = locale.tr("No runners found.");
+    }
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(if search.is_empty() {
+            locale.tr("Whitelist")
+        } else {
+            locale.trf("Whitelist matching '{0}'", &[search.as_str()])
+        })
+        .description(description)
+        .footer(|f| {
+            f.text(locale.trf(
+                "Page {0}/{1} · {2} runners",
+                &[
+                    (page + 1).to_string().as_str(),
+                    page_count.to_string().as_str(),
+                    runners.len().to_string().as_str(),
+                ],
+            ))
+        });
+
+    let mut action_row = CreateActionRow::default();
+    action_row
+        .create_button(|b| {
+            b.style(ButtonStyle::Secondary)
+                .label(locale.tr("Previous"))
+                .custom_id(format!(
+                    "{}{}:{}",
+                    WHITELIST_PAGE_CUSTOM_ID_PREFIX,
+                    page.saturating_sub(1),
+                    search
+                ))
+                .disabled(page == 0)
+        })
+        .create_button(|b| {
+            b.style(ButtonStyle::Secondary)
+                .label(locale.tr("Next"))
+                .custom_id(format!(
+                    "{}{}:{}",
+                    WHITELIST_PAGE_CUSTOM_ID_PREFIX,
+                    page + 1,
+                    search
+                ))
+                .disabled(page + 1 >= page_count)
+        });
+    (embed, action_row)
+}
+
+pub fn get_focused_option(options: &[CommandDataOption]) -> Option<&CommandDataOption> {
+    for option in options {
+        if option.focused {
+            return Some(option);
+        }
+        if let Some(focused) = get_focused_option(&option.options) {
+            return Some(focused);
+        }
+    }
+    None
+}
+
+// Searches subcommands too, since autocomplete needs the other options the user filled in.
+pub fn find_option_value<'a>(options: &'a [CommandDataOption], name: &str) -> Option<&'a str> {
+    for option in options {
+        if option.name == name {
+            return option.value.as_ref().and_then(|value| value.as_str());
+        }
+        if let Some(value) = find_option_value(&option.options, name) {
+            return Some(value);
+        }
+    }
+    None
+}
+
+pub fn suggest_runners(guild_cache_entry: &GuildCacheEntry, partial: &str) -> Vec<String> {
+    let partial = partial.trim().to_lowercase();
+    let mut runners = guild_cache_entry
+        .player_whitelist
+        .keys()
+        .filter(|name| !is_uuid(name) && name.to_lowercase().contains(partial.as_str()))
+        .map(|name| name.to_owned())
+        .collect::<Vec<_>>();
+    runners.sort_by_key(|name| {
+        (
+            !name.to_lowercase().starts_with(partial.as_str()),
+            name.to_lowercase(),
+        )
+    });
+    runners.truncate(MAX_AUTOCOMPLETE_CHOICES);
+    runners
+}
+
+pub fn suggest_times(
+    guild_cache_entry: &GuildCacheEntry,
+    split: Option<Split>,
+    partial: &str,
+) -> Vec<String> {
+    let partial = partial.trim();
+    let mut times = guild_cache_entry
+        .roles
+        .iter()
+        .filter(|role_cache_entry| {
+            !role_cache_entry.role.name.contains("PB")
+                && split
+                    .as_ref()
+                    .is_none_or(|split| &role_cache_entry.split == split)
+        })
+        .map(|role_cache_entry| (role_cache_entry.minutes, role_cache_entry.seconds))
+        .collect::<Vec<_>>();
+    times.sort();
+    // Pace roles are made in steps of 30 seconds, so those come after the existing ones.
+    for minutes in 1..=15 {
+        for seconds in [0, 30] {
+            times.push((minutes, seconds));
+        }
+    }
+    if let Ok(time) = parse_time_threshold(partial) {
+        times.insert(0, time);
+    }
+    let mut suggestions: Vec<String> = Vec::new();
+    for (minutes, seconds) in times {
+        let time = format!("{}:{:02}", minutes, seconds);
+        if time.starts_with(partial) && !suggestions.contains(&time) {
+            suggestions.push(time);
+        }
+    }
+    suggestions.truncate(MAX_AUTOCOMPLETE_CHOICES);
+    suggestions
+}
+
+// Suggests whitelisted runners for `ign`/`runner` options and pace times for `time` options.
+pub async fn autocomplete_options(context: &AutocompleteContext<'_>) -> Result<(), Box<dyn Error>> {
+    let options = &context.interaction.data.options;
+    let focused = match get_focused_option(options) {
+        Some(focused) => focused,
+        None => return Ok(()),
+    };
+    let partial = match focused.value.as_ref() {
+        Some(Value::String(value)) => value.to_owned(),
+        Some(value) => value.to_string(),
+        None => String::new(),
+    };
+    let split = find_option_value(options, "split").and_then(Split::from_str);
+    let choices = match context.cache.lock().await.entries.get(&context.guild_id) {
+        Some(guild_cache_entry) => match focused.name.as_str() {
+            "ign" | "runner" => suggest_runners(guild_cache_entry, partial.as_str()),
+            "time" => suggest_times(guild_cache_entry, split, partial.as_str()),
+            _ => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+    context
+        .interaction
+        .create_autocomplete_response(&context.ctx.http, |r| {
+            for choice in choices {
+                // Integer time options are in whole minutes.
+                if focused.kind == CommandOptionType::Integer {
+                    if let Some(minutes) = choice.strip_suffix(":00") {
+                        if let Ok(minutes) = minutes.parse::<i64>() {
+                            r.add_int_choice(choice.to_owned(), minutes);
+                        }
+                    }
+                } else {
+                    r.add_string_choice(choice.to_owned(), choice);
+                }
+            }
+            r
+        })
+        .await?;
+    Ok(())
+}
@@ -3,7 +3,7 @@ use std::error::Error;
 use serenity::{async_trait, builder::CreateApplicationCommand};
 
 use crate::{
-    command::{Command, CommandContext},
+    command::{Command, CommandContext, CommandOptions},
     config::{Config, PACEMANBOT_CHANNEL},
 };
 
@@ -19,6 +19,10 @@ impl Command for ValidateConfig {
         "Check if the current server configuration is valid and if the bot will work properly or not."
     }
 
+    fn changes_config(&self, _options: CommandOptions<'_>) -> bool {
+        false
+    }
+
     fn create_options<'a>(
         &self,
         command: &'a mut CreateApplicationCommand,
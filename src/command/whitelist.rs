@@ -2,18 +2,20 @@ use std::{collections::HashMap, error::Error};
 
 use serenity::{
     async_trait,
-    builder::CreateApplicationCommand,
+    builder::{CreateApplicationCommand, CreateEmbed},
     client::Context,
-    model::{
-        id::GuildId,
-        prelude::{application_command::ApplicationCommandInteraction, command::CommandOptionType},
-    },
+    model::{id::GuildId, prelude::command::CommandOptionType},
 };
 
 use crate::{
     cache::PlayerCacheEntry,
-    command::{get_new_config_contents, Command, CommandContext},
+    command::{
+        autocomplete_options, create_whitelist_page, get_guild_locale, get_new_config_contents,
+        AutocompleteContext, Command, CommandContext, CommandOptions, FromOptions,
+    },
     config::{extract_name_or_uuid_and_splits_from_config_line, PACEMANBOT_RUNNER_NAMES_CHANNEL},
+    locale::Locale,
+    resolver::UuidResolver,
 };
 
 pub struct Whitelist;
@@ -28,6 +30,14 @@ impl Command for Whitelist {
         "Whitelist new players or edit old players' configurations in the server based on ign."
     }
 
+    // Listing and showing runners don't change the whitelist.
+    fn changes_config(&self, options: CommandOptions<'_>) -> bool {
+        !matches!(
+            options.subcommand().map(|subcommand| subcommand.name),
+            Ok("list") | Ok("show")
+        )
+    }
+
     fn create_options<'a>(
         &self,
         command: &'a mut CreateApplicationCommand,
@@ -35,82 +45,238 @@ impl Command for Whitelist {
         command
             .create_option(|option| {
                 option
-                    .name("action")
-                    .description("Action to perform out of 'add_or_update' or 'remove'.")
-                    .required(true)
-                    .kind(CommandOptionType::String)
-                    .add_string_choice("Add or Update", "add_or_update")
-                    .add_string_choice("Remove", "remove")
-            })
-            .create_option(|option| {
-                option
-                    .name("ign")
-                    .description("In-game name of the runner that you want to add.")
-                    .required(true)
-                    .kind(CommandOptionType::String)
-            })
-            .create_option(|option| {
-                option
-                    .name("first_structure")
-                    .description(
-                        "The time for first structure that you want to setup for the runner.",
-                    )
-                    .kind(CommandOptionType::Integer)
-            })
-            .create_option(|option| {
-                option
-                    .name("second_structure")
-                    .description(
-                        "The time for second structure that you want to setup for the runner.",
-                    )
-                    .kind(CommandOptionType::Integer)
-            })
-            .create_option(|option| {
-                option
-                    .name("blind")
-                    .description("The time for blind that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .name("add_or_update")
+                    .description("Add a runner or update their splits.")
+                    .kind(CommandOptionType::SubCommand)
+                    .create_sub_option(|o| {
+                        o.name("ign")
+                            .description("In-game name of the runner that you want to add.")
+                            .set_autocomplete(true)
+                            .required(true)
+                            .kind(CommandOptionType::String)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("first_structure")
+                            .description(
+                                "The time for first structure that you want to setup for the runner.",
+                            )
+                            .kind(CommandOptionType::Integer)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("second_structure")
+                            .description(
+                                "The time for second structure that you want to setup for the runner.",
+                            )
+                            .kind(CommandOptionType::Integer)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("blind")
+                            .description("The time for blind that you want to setup for the runner.")
+                            .kind(CommandOptionType::Integer)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("eye_spy")
+                            .description(
+                                "The time for eye spy that you want to setup for the runner.",
+                            )
+                            .kind(CommandOptionType::Integer)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("end_enter")
+                            .description(
+                                "The time for end enter that you want to setup for the runner.",
+                            )
+                            .kind(CommandOptionType::Integer)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("finish")
+                            .description(
+                                "The time for completion that you want to setup for the runner(optional).",
+                            )
+                            .kind(CommandOptionType::Integer)
+                    })
+                    .create_sub_option(|o| {
+                        o.name("display_name")
+                            .description(
+                                "Name to show in pace messages instead of the ign, - removes it (optional).",
+                            )
+                            .kind(CommandOptionType::String)
+                    })
             })
             .create_option(|option| {
                 option
-                    .name("eye_spy")
-                    .description("The time for eye spy that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .name("remove")
+                    .description("Remove a runner from the whitelist.")
+                    .kind(CommandOptionType::SubCommand)
+                    .create_sub_option(|o| {
+                        o.name("ign")
+                            .description("In-game name of the runner that you want to remove.")
+                            .set_autocomplete(true)
+                            .required(true)
+                            .kind(CommandOptionType::String)
+                    })
             })
             .create_option(|option| {
                 option
-                    .name("end_enter")
-                    .description("The time for end enter that you want to setup for the runner.")
-                    .kind(CommandOptionType::Integer)
+                    .name("list")
+                    .description("List the whitelisted runners.")
+                    .kind(CommandOptionType::SubCommand)
+                    .create_sub_option(|o| {
+                        o.name("search")
+                            .description("Only list runners whose name contains this.")
+                            .kind(CommandOptionType::String)
+                    })
             })
             .create_option(|option| {
                 option
-                    .name("finish")
-                    .description(
-                        "The time for completion that you want to setup for the runner(optional).",
-                    )
-                    .kind(CommandOptionType::Integer)
+                    .name("show")
+                    .description("Show the splits of a whitelisted runner.")
+                    .kind(CommandOptionType::SubCommand)
+                    .create_sub_option(|o| {
+                        o.name("runner")
+                            .description("In-game name or uuid of the runner.")
+                            .set_autocomplete(true)
+                            .required(true)
+                            .kind(CommandOptionType::String)
+                    })
             })
     }
 
     async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
-        let response_content =
-            update_whitelist(context.ctx, context.guild_id, context.interaction, false).await?;
-        context
-            .interaction
-            .edit_original_interaction_response(&context.ctx.http, |data| {
-                data.content(response_content)
-            })
-            .await?;
+        let ctx = context.ctx;
+        let command = context.interaction;
+        let subcommand = context.options().subcommand()?;
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        match subcommand.name {
+            "list" => {
+                let search = subcommand.options.string("search")?.unwrap_or_default();
+                let players = get_cached_whitelist(&context).await?;
+                let (embed, action_row) =
+                    create_whitelist_page(&players, 0, search.as_str(), locale);
+                command
+                    .edit_original_interaction_response(&ctx.http, |data| {
+                        data.set_embed(embed)
+                            .components(|c| c.add_action_row(action_row))
+                    })
+                    .await?;
+            }
+            "show" => {
+                let runner = subcommand.options.required_string("runner")?;
+                let players = get_cached_whitelist(&context).await?;
+                let (name, player) = match players.iter().find(|(name, player)| {
+                    name.to_lowercase() == runner.to_lowercase()
+                        || player.uuid.as_ref() == Some(&runner.to_lowercase())
+                }) {
+                    Some(entry) => entry,
+                    None => {
+                        return Err(format!("runner: '{}' is not in the whitelist.", runner).into())
+                    }
+                };
+                let mut embed = CreateEmbed::default();
+                embed
+                    .title(name)
+                    .description(player.thresholds_to_str(locale));
+                if let Some(display_name) = player.display_name.as_ref() {
+                    embed.field(locale.tr("Display name"), display_name, false);
+                }
+                if let Some(uuid) = player.uuid.as_ref() {
+                    embed.field("UUID", uuid, false);
+                }
+                command
+                    .edit_original_interaction_response(&ctx.http, |data| data.set_embed(embed))
+                    .await?;
+            }
+            action => {
+                let options = subcommand.options.parse()?;
+                let response_content = update_whitelist(
+                    ctx,
+                    context.guild_id,
+                    context.resolver.as_ref(),
+                    action,
+                    options,
+                    false,
+                    locale,
+                )
+                .await?;
+                command
+                    .edit_original_interaction_response(&ctx.http, |data| {
+                        data.content(response_content)
+                    })
+                    .await?;
+            }
+        }
         Ok(())
     }
+
+    async fn autocomplete(&self, context: AutocompleteContext<'_>) -> Result<(), Box<dyn Error>> {
+        autocomplete_options(&context).await
+    }
+}
+
+async fn get_cached_whitelist(
+    context: &CommandContext<'_>,
+) -> Result<HashMap<String, PlayerCacheEntry>, Box<dyn Error>> {
+    let locked_cache = context.cache.lock().await;
+    match locked_cache.entries.get(&context.guild_id) {
+        Some(guild_cache_entry) => Ok(guild_cache_entry.player_whitelist.to_owned()),
+        None => Err(format!(
+            "failed to get cache entry for guild id: {}.",
+            context.guild_id
+        )
+        .into()),
+    }
+}
+
+pub(super) struct WhitelistOptions {
+    ign: String,
+    uuid: String,
+    splits_data: PlayerCacheEntry,
+    // None when the option is omitted, which keeps the current display name.
+    display_name: Option<String>,
+}
+
+impl FromOptions for WhitelistOptions {
+    fn from_options(options: CommandOptions<'_>) -> Result<Self, Box<dyn Error>> {
+        let splits_data = PlayerCacheEntry {
+            first_structure: options.minutes("first_structure")?.unwrap_or_default(),
+            second_structure: options.minutes("second_structure")?.unwrap_or_default(),
+            blind: options.minutes("blind")?.unwrap_or_default(),
+            eye_spy: options.minutes("eye_spy")?.unwrap_or_default(),
+            end_enter: options.minutes("end_enter")?.unwrap_or_default(),
+            finish: options.minutes("finish")?,
+            ..Default::default()
+        };
+        Ok(Self {
+            ign: options.string("ign")?.unwrap_or_default(),
+            uuid: options.string("uuid")?.unwrap_or_default(),
+            splits_data,
+            display_name: options
+                .string("display_name")?
+                .map(|display_name| display_name.trim().to_string()),
+        })
+    }
+}
+
+// An omitted display name keeps the current one and `-` removes it.
+fn get_display_name(
+    display_name: Option<String>,
+    current: Option<&PlayerCacheEntry>,
+) -> Option<String> {
+    match display_name.as_deref() {
+        Some("") | Some("-") => None,
+        Some(_) => display_name,
+        None => current.and_then(|player_data| player_data.display_name.to_owned()),
+    }
 }
 
 pub(super) async fn update_whitelist(
     ctx: &Context,
     guild_id: GuildId,
-    command: &ApplicationCommandInteraction,
+    resolver: &dyn UuidResolver,
+    action: &str,
+    options: WhitelistOptions,
     use_uuid: bool,
+    locale: Locale,
 ) -> Result<String, Box<dyn Error>> {
     let channels = match ctx.cache.guild_channels(guild_id) {
         Some(channels) => channels,
@@ -118,163 +284,81 @@ pub(super) async fn update_whitelist(
             return Err(format!("failed to get channels for guild id: {}", guild_id).into());
         }
     };
-    let mut action = String::new();
-    let mut ign = String::new();
-    let mut uuid = String::new();
-    let mut splits_data = PlayerCacheEntry::default();
+    let WhitelistOptions {
+        ign,
+        uuid,
+        mut splits_data,
+        display_name,
+    } = options;
 
-    for option in command.data.options.iter() {
-        match option.name.as_str() {
-            "action" => {
-                action = match option.value.to_owned() {
-                    Some(value) => match value.as_str() {
-                        Some(str) => str.to_owned(),
-                        None => {
-                            return Err(
-                                String::from("failed to parse string for action option.").into()
-                            )
-                        }
-                    },
-                    None => {
-                        return Err(String::from("failed to get value for action option.").into())
-                    }
-                }
+    // Stores both the name and the uuid when possible, so that the entry keeps
+    // working after a name change. Failing to resolve doesn't block whitelisting.
+    let mut response_content = locale.tr("Updated config!");
+    let uuid = uuid.to_lowercase();
+    let runner = if action == "remove" {
+        if use_uuid {
+            uuid.to_owned()
+        } else {
+            // Entries stored as `name@uuid` under an old name are matched by the uuid.
+            if let Ok(Some(profile)) = resolver.resolve_uuid(ign.as_str()).await {
+                splits_data.uuid = Some(profile.uuid);
             }
-            "ign" => match option.value.to_owned() {
-                Some(value) => {
-                    ign = match value.as_str() {
-                        Some(str) => str.to_owned(),
-                        None => {
-                            return Err(
-                                String::from("failed to parse string for ign option.").into()
-                            )
-                        }
-                    }
-                }
-                None => return Err(String::from("failed to get value for ign option.").into()),
-            },
-            "uuid" => match option.value.to_owned() {
-                Some(value) => {
-                    uuid = match value.as_str() {
-                        Some(str) => str.to_owned(),
-                        None => {
-                            return Err(
-                                String::from("failed to parse string for uuid option.").into()
-                            )
-                        }
-                    }
-                }
-                None => return Err(String::from("failed to get value for uuid option.").into()),
-            },
-            "first_structure" => match option.value.to_owned() {
-                Some(value) => {
-                    splits_data.first_structure = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from(
-                                "failed to parse u64 for first structure option.",
-                            )
-                            .into())
-                        }
-                    }
-                }
-                None => {
-                    if action != "remove" {
-                        return Err(String::from(
-                            "failed to get value for first structure option.",
-                        )
-                        .into());
-                    }
-                }
-            },
-            "second_structure" => match option.value.to_owned() {
-                Some(value) => {
-                    splits_data.second_structure = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from(
-                                "failed to parse u64 for second structure option.",
-                            )
-                            .into())
-                        }
-                    }
-                }
-                None => {
-                    if action != "remove" {
-                        return Err(String::from(
-                            "failed to get value for second structure option.",
-                        )
-                        .into());
-                    }
-                }
-            },
-            "blind" => match option.value.to_owned() {
-                Some(value) => {
-                    splits_data.blind = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(String::from("failed to parse u64 for blind option.").into())
-                        }
-                    }
-                }
-                None => {
-                    if action != "remove" {
-                        return Err(String::from("failed to get value for blind option.").into());
-                    }
-                }
-            },
-            "eye_spy" => match option.value.to_owned() {
-                Some(value) => {
-                    splits_data.eye_spy = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(
-                                String::from("failed to parse u64 for eye spy option.").into()
-                            )
-                        }
-                    }
-                }
-                None => {
-                    if action != "remove" {
-                        return Err(String::from("failed to get value for eye spy option.").into());
-                    }
-                }
-            },
-            "end_enter" => match option.value.to_owned() {
-                Some(value) => {
-                    splits_data.end_enter = match value.as_u64() {
-                        Some(int) => int as u8,
-                        None => {
-                            return Err(
-                                String::from("failed to parse u64 for end enter option.").into()
-                            )
-                        }
-                    }
-                }
-                None => {
-                    if action != "remove" {
-                        return Err(
-                            String::from("failed to get value for end enter option.").into()
-                        );
-                    }
-                }
-            },
-            "finish" => match option.value.to_owned() {
-                Some(value) => {
-                    splits_data.finish = match value.as_u64() {
-                        Some(int) => Some(int as u8),
-                        None => {
-                            return Err(
-                                String::from("failed to parse u64 for end enter option.").into()
-                            )
-                        }
-                    }
-                }
-                None => splits_data.finish = None,
-            },
-            _ => return Err(format!("unrecognized command option: '{}'", option.name).into()),
-        };
-    }
+            ign.to_owned()
+        }
+    } else if use_uuid {
+        match resolver.resolve_name(uuid.as_str()).await {
+            Ok(Some(profile)) => {
+                splits_data.uuid = Some(uuid.to_owned());
+                profile.name
+            }
+            Ok(None) => {
+                response_content = locale.trf(
+                    "Updated config, but no minecraft account was found for uuid: '{0}'.",
+                    &[uuid.as_str()],
+                );
+                uuid.to_owned()
+            }
+            Err(err) => {
+                response_content = locale.trf(
+                    "Updated config, but failed to resolve the name for uuid: '{0}': {1}",
+                    &[uuid.as_str(), err.to_string().as_str()],
+                );
+                uuid.to_owned()
+            }
+        }
+    } else {
+        match resolver.resolve_uuid(ign.as_str()).await {
+            Ok(Some(profile)) => {
+                splits_data.uuid = Some(profile.uuid);
+                profile.name
+            }
+            Ok(None) => {
+                response_content = locale.trf(
+                    "Updated config, but no minecraft account was found for: '{0}'.",
+                    &[ign.as_str()],
+                );
+                ign.to_owned()
+            }
+            Err(err) => {
+                response_content = locale.trf(
+                    "Updated config, but failed to resolve the uuid for: '{0}': {1}",
+                    &[ign.as_str(), err.to_string().as_str()],
+                );
+                ign.to_owned()
+            }
+        }
+    };
+    let runner_uuid = if use_uuid {
+        Some(uuid.to_owned())
+    } else {
+        splits_data.uuid.to_owned()
+    };
+    let is_same_runner = |name: &String, player_data: &PlayerCacheEntry| {
+        name.to_lowercase() == runner.to_lowercase()
+            || runner_uuid.as_ref().is_some_and(|runner_uuid| {
+                name == runner_uuid || player_data.uuid.as_ref() == Some(runner_uuid)
+            })
+    };
 
     let channel = channels
         .iter()
@@ -308,18 +392,22 @@ pub(super) async fn update_whitelist(
                 let (name, split_data) = extract_name_or_uuid_and_splits_from_config_line(line)?;
                 players.insert(name, split_data);
             }
-            if action == "remove" {
-                if use_uuid {
-                    players.remove(&uuid);
-                } else {
-                    players.remove(&ign);
-                }
-            } else {
-                if use_uuid {
-                    players.insert(uuid, splits_data);
-                } else {
-                    players.insert(ign, splits_data);
-                }
+            let current = players
+                .iter()
+                .find(|(name, player_data)| is_same_runner(name, player_data))
+                .map(|(_, player_data)| player_data);
+            splits_data.display_name = get_display_name(display_name, current);
+            let runner_count = players.len();
+            players.retain(|name, player_data| !is_same_runner(name, player_data));
+            if action == "remove" && players.len() == runner_count {
+                return Err(format!(
+                    "runner: '{}' was not found in #{}.",
+                    runner, PACEMANBOT_RUNNER_NAMES_CHANNEL
+                )
+                .into());
+            }
+            if action != "remove" {
+                players.insert(runner, splits_data);
             }
             let new_config = get_new_config_contents(players);
             message
@@ -335,11 +423,8 @@ pub(super) async fn update_whitelist(
                     format!("failed to remove names from in guild id: {}", guild_id).into(),
                 );
             }
-            if use_uuid {
-                players.insert(uuid, splits_data);
-            } else {
-                players.insert(ign, splits_data);
-            }
+            splits_data.display_name = get_display_name(display_name, None);
+            players.insert(runner, splits_data);
             let new_config = get_new_config_contents(players);
             channel
                 .send_message(&ctx.http, |m| {
@@ -348,7 +433,7 @@ pub(super) async fn update_whitelist(
                 .await?;
         }
     };
-    Ok("Updated config!".to_string())
+    Ok(response_content)
 }
 
 pub const WHITELIST: Whitelist = Whitelist {};
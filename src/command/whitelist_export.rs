@@ -0,0 +1,90 @@
+use std::{borrow::Cow, error::Error};
+
+use serenity::{
+    async_trait,
+    builder::CreateApplicationCommand,
+    model::{channel::AttachmentType, prelude::command::CommandOptionType},
+};
+
+use crate::{
+    command::{
+        get_config_message, get_guild_locale, parse_whitelist, Command, CommandContext,
+        CommandOptions,
+    },
+    config::{whitelist_to_csv, whitelist_to_json, PACEMANBOT_RUNNER_NAMES_CHANNEL},
+};
+
+pub struct WhitelistExport;
+
+#[async_trait]
+impl Command for WhitelistExport {
+    fn name(&self) -> &str {
+        "whitelist_export"
+    }
+
+    fn description(&self) -> &str {
+        "Export the whitelist as a csv or json file."
+    }
+
+    fn changes_config(&self, _options: CommandOptions<'_>) -> bool {
+        false
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.create_option(|option| {
+            option
+                .name("format")
+                .description("Format of the exported file, defaults to csv.")
+                .kind(CommandOptionType::String)
+                .add_string_choice("CSV", "csv")
+                .add_string_choice("JSON", "json")
+        })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+
+        let mut format = "csv";
+        for option in command.data.options.iter() {
+            if option.name == "format" {
+                format = option
+                    .value
+                    .as_ref()
+                    .and_then(|value| value.as_str())
+                    .unwrap_or("csv");
+            }
+        }
+        let (_, message) =
+            get_config_message(ctx, &context.guild_id, PACEMANBOT_RUNNER_NAMES_CHANNEL).await?;
+        let players = parse_whitelist(&message)?;
+        let contents = match format {
+            "json" => whitelist_to_json(&players)?,
+            _ => whitelist_to_csv(&players),
+        };
+
+        command
+            .create_followup_message(&ctx.http, |f| {
+                f.ephemeral(true).add_file(AttachmentType::Bytes {
+                    data: Cow::from(contents.into_bytes()),
+                    filename: format!("whitelist.{}", format),
+                })
+            })
+            .await?;
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        command
+            .edit_original_interaction_response(&ctx.http, |m| {
+                m.content(locale.trf(
+                    "Exported {0} runners.",
+                    &[players.len().to_string().as_str()],
+                ))
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub const WHITELIST_EXPORT: WhitelistExport = WhitelistExport {};
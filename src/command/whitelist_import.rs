@@ -0,0 +1,206 @@
+use std::{collections::HashMap, error::Error, time::Instant};
+
+use serenity::{
+    async_trait,
+    builder::{CreateActionRow, CreateApplicationCommand},
+    model::prelude::{command::CommandOptionType, component::ButtonStyle},
+};
+
+use crate::{
+    cache::{PendingImport, PlayerCacheEntry},
+    command::{
+        get_config_message, get_guild_locale, get_new_config_contents, parse_whitelist, Command,
+        CommandContext,
+    },
+    config::{parse_whitelist_file, PACEMANBOT_RUNNER_NAMES_CHANNEL},
+};
+
+// Leaves room for the header and the config code block in a 2000 character message.
+const MAX_PREVIEW_LENGTH: usize = 1800;
+const MAX_CONFIG_LENGTH: usize = 1990;
+
+pub struct WhitelistImport;
+
+fn format_player(player: &PlayerCacheEntry) -> String {
+    let mut splits = format!(
+        "{}/{}/{}/{}/{}",
+        player.first_structure,
+        player.second_structure,
+        player.blind,
+        player.eye_spy,
+        player.end_enter
+    );
+    if let Some(finish) = player.finish {
+        splits = format!("{}/{}", splits, finish);
+    }
+    match player.display_name.as_ref() {
+        Some(display_name) => format!("{} ({})", splits, display_name),
+        None => splits,
+    }
+}
+
+fn find_runner<'a>(
+    players: &'a HashMap<String, PlayerCacheEntry>,
+    runner: &str,
+) -> Option<(&'a String, &'a PlayerCacheEntry)> {
+    players
+        .iter()
+        .find(|(name, _)| name.to_lowercase() == runner.to_lowercase())
+}
+
+#[async_trait]
+impl Command for WhitelistImport {
+    fn name(&self) -> &str {
+        "whitelist_import"
+    }
+
+    fn description(&self) -> &str {
+        "Import runners into the whitelist from a csv or json file."
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command
+            .create_option(|option| {
+                option
+                    .name("file")
+                    .description("A .csv or .json file in the format of /whitelist_export.")
+                    .required(true)
+                    .kind(CommandOptionType::Attachment)
+            })
+            .create_option(|option| {
+                option
+                    .name("mode")
+                    .description("Whether to merge into or replace the current whitelist, defaults to merge.")
+                    .kind(CommandOptionType::String)
+                    .add_string_choice("Merge", "merge")
+                    .add_string_choice("Replace", "replace")
+            })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+
+        let options = context.options();
+        let replace = options.string("mode")?.as_deref() == Some("replace");
+        let attachment = match options.attachment("file")? {
+            Some(attachment) => attachment,
+            None => return Err(String::from("failed to get attachment for file option.").into()),
+        };
+        let contents = String::from_utf8(attachment.download().await?)?;
+        let imported_players = parse_whitelist_file(attachment.filename.as_str(), &contents)?;
+
+        let (_, message) =
+            get_config_message(ctx, &context.guild_id, PACEMANBOT_RUNNER_NAMES_CHANNEL).await?;
+        let current_players = parse_whitelist(&message)?;
+        let mut players = if replace {
+            HashMap::new()
+        } else {
+            current_players.clone()
+        };
+        for (runner, player) in imported_players.iter() {
+            players.retain(|name, _| name.to_lowercase() != runner.to_lowercase());
+            players.insert(runner.to_owned(), player.to_owned());
+        }
+
+        let mut runners = imported_players.keys().collect::<Vec<_>>();
+        runners.sort_by_key(|runner| runner.to_lowercase());
+        let mut changes = Vec::new();
+        let mut unchanged = 0;
+        for runner in runners {
+            let player = &imported_players[runner];
+            match find_runner(&current_players, runner) {
+                Some((_, current)) if current == player => unchanged += 1,
+                Some((_, current)) => changes.push(format!(
+                    "~ {}: {} -> {}",
+                    runner,
+                    format_player(current),
+                    format_player(player)
+                )),
+                None => changes.push(format!("+ {}: {}", runner, format_player(player))),
+            }
+        }
+        let mut removed = current_players
+            .iter()
+            .filter(|(runner, _)| find_runner(&players, runner).is_none())
+            .map(|(runner, player)| format!("- {}: {}", runner, format_player(player)))
+            .collect::<Vec<_>>();
+        removed.sort_by_key(|line| line.to_lowercase());
+        changes.extend(removed);
+
+        let new_contents = get_new_config_contents(players.to_owned());
+        if new_contents.len() > MAX_CONFIG_LENGTH {
+            return Err(format!(
+                "the imported whitelist is too long to fit in #{} ({} characters).",
+                PACEMANBOT_RUNNER_NAMES_CHANNEL,
+                new_contents.len()
+            )
+            .into());
+        }
+
+        let mut preview = String::new();
+        for (idx, change) in changes.iter().enumerate() {
+            if preview.len() + change.len() > MAX_PREVIEW_LENGTH {
+                preview = format!("{}... and {} more\n", preview, changes.len() - idx);
+                break;
+            }
+            preview = format!("{}{}\n", preview, change);
+        }
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        let response_content = if changes.is_empty() {
+            locale.trf(
+                "Nothing to import, all {0} runners are unchanged.",
+                &[unchanged.to_string().as_str()],
+            )
+        } else {
+            format!(
+                "{}\n```diff\n{}```",
+                locale.trf(
+                    "Whitelist import preview ({0} unchanged):",
+                    &[unchanged.to_string().as_str()]
+                ),
+                preview
+            )
+        };
+        if changes.is_empty() {
+            command
+                .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+                .await?;
+            return Ok(());
+        }
+
+        context.cache.lock().await.pending_imports.insert(
+            (context.guild_id, command.user.id),
+            PendingImport {
+                players,
+                base_contents: get_new_config_contents(current_players),
+                created_at: Instant::now(),
+            },
+        );
+
+        let mut confirm_action_row = CreateActionRow::default();
+        confirm_action_row
+            .create_button(|b| {
+                b.style(ButtonStyle::Success)
+                    .label(locale.tr("Confirm"))
+                    .custom_id("whitelist_import_confirm")
+            })
+            .create_button(|b| {
+                b.style(ButtonStyle::Danger)
+                    .label(locale.tr("Cancel"))
+                    .custom_id("whitelist_import_cancel")
+            });
+        command
+            .edit_original_interaction_response(&ctx.http, |m| {
+                m.content(response_content)
+                    .components(|c| c.add_action_row(confirm_action_row))
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+pub const WHITELIST_IMPORT: WhitelistImport = WhitelistImport {};
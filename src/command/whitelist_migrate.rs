@@ -0,0 +1,129 @@
+use std::{collections::HashMap, error::Error};
+
+use serenity::{
+    async_trait, builder::CreateApplicationCommand, model::prelude::command::CommandOptionType,
+};
+
+use crate::{
+    command::{
+        get_config_message, get_guild_locale, get_new_config_contents, parse_whitelist,
+        set_config_message_contents, Command, CommandContext,
+    },
+    config::PACEMANBOT_RUNNER_NAMES_CHANNEL,
+    resolver::{hyphenate_uuid, is_uuid},
+};
+
+pub struct WhitelistMigrate;
+
+#[async_trait]
+impl Command for WhitelistMigrate {
+    fn name(&self) -> &str {
+        "whitelist_migrate"
+    }
+
+    fn description(&self) -> &str {
+        "Resolve and store the uuids of all whitelisted runners, so that name changes don't break them."
+    }
+
+    fn create_options<'a>(
+        &self,
+        command: &'a mut CreateApplicationCommand,
+    ) -> &'a mut CreateApplicationCommand {
+        command.create_option(|option| {
+            option
+                .name("refresh_names")
+                .description("Also update the names of runners whose uuid is already known.")
+                .kind(CommandOptionType::Boolean)
+        })
+    }
+
+    async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
+        let ctx = context.ctx;
+        let command = context.interaction;
+        let resolver = context.resolver.as_ref();
+
+        let refresh_names = context.options().boolean("refresh_names")?.unwrap_or(false);
+        let (channel, message) =
+            get_config_message(ctx, &context.guild_id, PACEMANBOT_RUNNER_NAMES_CHANNEL).await?;
+        let players = parse_whitelist(&message)?;
+
+        let names = players
+            .iter()
+            .filter(|(name, player)| player.uuid.is_none() && !is_uuid(name))
+            .map(|(name, _)| name.to_owned())
+            .collect::<Vec<_>>();
+        let profiles = resolver.resolve_uuids(&names).await?;
+
+        let mut new_players = HashMap::new();
+        let mut migrated = 0;
+        let mut renamed = 0;
+        let mut not_found = Vec::new();
+        for (name, mut player) in players {
+            let old_name = name.to_owned();
+            let uuid = match player.uuid.to_owned() {
+                Some(uuid) => Some(uuid),
+                None if is_uuid(name.as_str()) => Some(hyphenate_uuid(name.as_str())),
+                None => None,
+            };
+            let new_name = match uuid {
+                // Uuid only entries always get a name, the others only when asked to.
+                Some(uuid) if player.uuid.is_none() || refresh_names => {
+                    match resolver.resolve_name(uuid.as_str()).await? {
+                        Some(profile) => {
+                            if player.uuid.is_none() {
+                                migrated += 1;
+                            }
+                            player.uuid = Some(uuid);
+                            profile.name
+                        }
+                        None => {
+                            not_found.push(name.to_owned());
+                            name
+                        }
+                    }
+                }
+                Some(_) => name,
+                None => match profiles.get(&name.to_lowercase()) {
+                    Some(profile) => {
+                        migrated += 1;
+                        player.uuid = Some(profile.uuid.to_owned());
+                        profile.name.to_owned()
+                    }
+                    None => {
+                        not_found.push(name.to_owned());
+                        name
+                    }
+                },
+            };
+            if !is_uuid(old_name.as_str()) && new_name.to_lowercase() != old_name.to_lowercase() {
+                renamed += 1;
+            }
+            new_players.insert(new_name, player);
+        }
+        set_config_message_contents(ctx, &channel, message, get_new_config_contents(new_players))
+            .await?;
+
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        let mut response_content = locale.trf(
+            "Stored the uuids of {0} runners and updated the names of {1} runners.",
+            &[migrated.to_string().as_str(), renamed.to_string().as_str()],
+        );
+        if !not_found.is_empty() {
+            not_found.sort_by_key(|name| name.to_lowercase());
+            response_content = format!(
+                "{}\n{}",
+                response_content,
+                locale.trf(
+                    "No minecraft account found for: {0}.",
+                    &[not_found.join(", ").as_str()]
+                )
+            );
+        }
+        command
+            .edit_original_interaction_response(&ctx.http, |m| m.content(response_content))
+            .await?;
+        Ok(())
+    }
+}
+
+pub const WHITELIST_MIGRATE: WhitelistMigrate = WhitelistMigrate {};
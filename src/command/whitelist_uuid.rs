@@ -6,7 +6,7 @@ use serenity::{
 
 use super::whitelist::update_whitelist;
 
-use crate::command::{Command, CommandContext};
+use crate::command::{get_guild_locale, Command, CommandContext};
 
 pub struct WhitelistUUID;
 
@@ -83,11 +83,28 @@ impl Command for WhitelistUUID {
                     )
                     .kind(CommandOptionType::Integer)
             })
+            .create_option(|option| {
+                option
+                    .name("display_name")
+                    .description("Name to show in pace messages instead of the ign, - removes it (optional).")
+                    .kind(CommandOptionType::String)
+            })
     }
 
     async fn execute(&self, context: CommandContext<'_>) -> Result<(), Box<dyn Error>> {
-        let response_content =
-            update_whitelist(context.ctx, context.guild_id, context.interaction, true).await?;
+        let action = context.options().required_string("action")?;
+        let options = context.options().parse()?;
+        let locale = get_guild_locale(&context.cache, &context.guild_id).await;
+        let response_content = update_whitelist(
+            context.ctx,
+            context.guild_id,
+            context.resolver.as_ref(),
+            action.as_str(),
+            options,
+            true,
+            locale,
+        )
+        .await?;
         context
             .interaction
             .edit_original_interaction_response(&context.ctx.http, |data| {
@@ -6,11 +6,15 @@ use serenity::{
 };
 
 use crate::{
-    cache::{GuildCacheEntry, PlayerCacheEntry, RoleCacheEntry, Split},
+    cache::{
+        GuildCacheEntry, GuildSettings, PingStateEntry, PlayerCacheEntry, RoleCacheEntry, Split,
+    },
     config::{
-        extract_name_or_uuid_and_splits_from_config_line, extract_split_from_pb_role_name,
-        extract_split_from_role_name, extract_splits_and_name_from_role_name, PACEMANBOT_CHANNEL,
-        PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL, PACEMANBOT_RUNNER_NAMES_CHANNEL, ROLE_PREFIX,
+        extract_name_or_uuid_and_splits_from_config_line, extract_setting_from_config_line,
+        extract_split_from_pb_role_name, extract_split_from_role_name,
+        extract_splits_and_name_from_role_name, extract_subscription_from_config_line,
+        PACEMANBOT_CHANNEL, PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL, PACEMANBOT_RUNNER_NAMES_CHANNEL,
+        PACEMANBOT_SETTINGS_CHANNEL, PACEMANBOT_SUBSCRIPTIONS_CHANNEL, ROLE_PREFIX,
         ROLE_PREFIX_115, ROLE_PREFIX_17, ROLE_PREFIX_AA,
     },
 };
@@ -106,12 +110,59 @@ impl Config {
             };
             roles.push(role_data);
         }
+
+        let mut settings = GuildSettings::default();
+        if let Some(settings_channel) = channels
+            .iter()
+            .find(|c| c.name == PACEMANBOT_SETTINGS_CHANNEL)
+        {
+            let messages = settings_channel.messages(&ctx.http, |m| m.limit(1)).await?;
+            if let Some(message) = messages.last() {
+                for line in message.content.split("\n") {
+                    if line == "```" || line.is_empty() {
+                        continue;
+                    }
+                    let (key, value) = extract_setting_from_config_line(line)?;
+                    match settings.set(key.as_str(), value.as_str()) {
+                        Ok(_) => (),
+                        Err(err) => {
+                            return Err(format!(
+                                "failed to parse #{} in guild name: {} due to: {}",
+                                PACEMANBOT_SETTINGS_CHANNEL, name, err
+                            )
+                            .into())
+                        }
+                    };
+                }
+            }
+        }
+
+        let mut subscriptions = Vec::new();
+        if let Some(subscriptions_channel) = channels
+            .iter()
+            .find(|c| c.name == PACEMANBOT_SUBSCRIPTIONS_CHANNEL)
+        {
+            let messages = subscriptions_channel
+                .messages(&ctx.http, |m| m.limit(1))
+                .await?;
+            if let Some(message) = messages.last() {
+                for line in message.content.split("\n") {
+                    if line == "```" || line.is_empty() {
+                        continue;
+                    }
+                    subscriptions.push(extract_subscription_from_config_line(line)?);
+                }
+            }
+        }
         Ok(GuildCacheEntry {
             name,
             pace_channel,
             lb_channel,
             player_whitelist: players,
             roles,
+            settings,
+            subscriptions,
+            ping_state: PingStateEntry::default(),
         })
     }
 
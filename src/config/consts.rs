@@ -1,7 +1,10 @@
-pub const ROLE_PREFIX: &'static str = "*";
-pub const ROLE_PREFIX_115: &'static str = "*115";
-pub const ROLE_PREFIX_17: &'static str = "*17";
-pub const ROLE_PREFIX_AA: &'static str = "*aa";
-pub const PACEMANBOT_CHANNEL: &'static str = "pacemanbot";
-pub const PACEMANBOT_RUNNER_NAMES_CHANNEL: &'static str = "pacemanbot-runner-names";
-pub const PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL: &'static str = "pacemanbot-runner-leaderboard";
+pub const ROLE_PREFIX: &str = "*";
+pub const ROLE_PREFIX_115: &str = "*115";
+pub const ROLE_PREFIX_17: &str = "*17";
+pub const ROLE_PREFIX_AA: &str = "*aa";
+pub const PACEMANBOT_CHANNEL: &str = "pacemanbot";
+pub const PACEMANBOT_RUNNER_NAMES_CHANNEL: &str = "pacemanbot-runner-names";
+pub const PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL: &str = "pacemanbot-runner-leaderboard";
+pub const PACEMANBOT_SETTINGS_CHANNEL: &str = "pacemanbot-settings";
+pub const PACEMANBOT_SUBSCRIPTIONS_CHANNEL: &str = "pacemanbot-subscriptions";
+pub const PACEMANBOT_AUDIT_CHANNEL: &str = "pacemanbot-audit";
@@ -1,7 +1,9 @@
 mod config;
 mod consts;
 mod parsers;
+mod whitelist_file;
 
 pub use config::Config;
 pub use consts::*;
 pub use parsers::*;
+pub use whitelist_file::*;
@@ -1,10 +1,15 @@
-use std::error::Error;
+use std::{collections::HashMap, error::Error};
 
 use regex::Regex;
+use serenity::model::id::{ChannelId, MessageId, UserId};
 
 use crate::{
-    cache::{PlayerCacheEntry, Split},
+    cache::{
+        PingWindow, PlayerCacheEntry, RoleMessage, Split, SubscriptionCacheEntry,
+        SubscriptionDelivery, EMBED_EMOJIS, EMBED_FIELDS, WEEKDAYS,
+    },
     config::ROLE_PREFIX,
+    ws::{Platform, PLATFORMS},
 };
 
 pub fn extract_split_from_role_name(role_name: &str) -> Result<(Split, u8, u8), Box<dyn Error>> {
@@ -134,13 +139,21 @@ pub fn extract_splits_and_name_from_role_name(
 pub fn extract_name_or_uuid_and_splits_from_config_line(
     line: &str,
 ) -> Result<(String, PlayerCacheEntry), Box<dyn Error>> {
+    let (line, display_name) = match line.split_once('|') {
+        Some((line, display_name)) => (line, Some(display_name.trim())),
+        None => (line, None),
+    };
     let line = line.trim();
     let line = line.replace(" ", "");
     let line_splits = line.split(':').collect::<Vec<&str>>();
     if line_splits.len() != 2 {
         return Err(format!("failed to parse line contents: '{}'.", line).into());
     }
-    let (player_name_or_uuid, splits_string) = (line_splits[0], line_splits[1]);
+    let (player_name_or_uuid, uuid) = match line_splits[0].split_once('@') {
+        Some((name, uuid)) => (name, Some(uuid.to_lowercase())),
+        None => (line_splits[0], None),
+    };
+    let splits_string = line_splits[1];
     let splits = splits_string.split('/').collect::<Vec<&str>>();
     if splits.len() != 5 && splits.len() != 6 {
         return Err(format!("failed to parse line contents: '{}'.", line).into());
@@ -165,5 +178,505 @@ pub fn extract_name_or_uuid_and_splits_from_config_line(
         };
         idx += 1;
     }
+    split_data.uuid = uuid;
+    split_data.display_name = display_name
+        .filter(|display_name| !display_name.is_empty())
+        .map(|display_name| display_name.to_string());
     Ok((player_name_or_uuid.to_string(), split_data))
 }
+
+pub fn extract_setting_from_config_line(line: &str) -> Result<(String, String), Box<dyn Error>> {
+    let line = line.trim();
+    let (key, value) = match line.split_once('=') {
+        Some(tup) => tup,
+        None => return Err(format!("failed to parse setting line: '{}'.", line).into()),
+    };
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+pub fn parse_bool_setting(key: &str, value: &str) -> Result<bool, Box<dyn Error>> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" => Ok(true),
+        "false" | "no" | "off" => Ok(false),
+        _ => Err(format!(
+            "failed to parse value: '{}' for setting: '{}' as true/false.",
+            value, key
+        )
+        .into()),
+    }
+}
+
+pub fn parse_number_setting(key: &str, value: &str) -> Result<u64, Box<dyn Error>> {
+    match value.trim().parse::<u64>() {
+        Ok(number) => Ok(number),
+        Err(err) => Err(format!(
+            "failed to parse value: '{}' for setting: '{}' as a number due to: {}",
+            value, key, err
+        )
+        .into()),
+    }
+}
+
+pub fn parse_embed_fields(key: &str, value: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    if value.trim().to_lowercase() == "all" {
+        return Ok(EMBED_FIELDS.iter().map(|field| field.to_string()).collect());
+    }
+    let mut fields = Vec::new();
+    for field in value.split(',') {
+        let field = field.trim().to_lowercase();
+        if field.is_empty() {
+            continue;
+        }
+        if !EMBED_FIELDS.contains(&field.as_str()) {
+            return Err(format!(
+                "failed to parse field: '{}' for setting: '{}', expected 'all' or any of {}.",
+                field,
+                key,
+                EMBED_FIELDS.join(", ")
+            )
+            .into());
+        }
+        fields.push(field);
+    }
+    Ok(fields)
+}
+
+pub fn parse_live_platforms(key: &str, value: &str) -> Result<Vec<Platform>, Box<dyn Error>> {
+    let mut platforms = Vec::new();
+    for platform in value.split(',') {
+        let platform = platform.trim().to_lowercase();
+        if platform.is_empty() {
+            continue;
+        }
+        match Platform::from_str(platform.as_str()) {
+            Some(platform) => platforms.push(platform),
+            None => {
+                return Err(format!(
+                    "failed to parse platform: '{}' for setting: '{}', expected any of {}.",
+                    platform,
+                    key,
+                    PLATFORMS
+                        .iter()
+                        .map(|platform| platform.to_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+                .into())
+            }
+        }
+    }
+    Ok(platforms)
+}
+
+pub fn parse_split_values(
+    key: &str,
+    value: &str,
+) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let mut split_values = HashMap::new();
+    if value.trim().to_lowercase() == "off" {
+        return Ok(split_values);
+    }
+    for split_value in value.split(',') {
+        let (split, value) = match split_value.split_once('=') {
+            Some((split, value)) => (split.trim().to_uppercase(), value.trim()),
+            None => {
+                return Err(format!(
+                    "failed to parse: '{}' for setting: '{}', expected format 'split=value'.",
+                    split_value, key
+                )
+                .into())
+            }
+        };
+        if split != "F" && Split::from_str(split.as_str()).is_none() {
+            return Err(format!(
+                "failed to parse split: '{}' for setting: '{}', expected one of FS, SS, B, E, EE or F.",
+                split, key
+            )
+            .into());
+        }
+        split_values.insert(split, value.to_string());
+    }
+    Ok(split_values)
+}
+
+pub fn parse_split_colors(key: &str, value: &str) -> Result<HashMap<String, u32>, Box<dyn Error>> {
+    let mut split_colors = HashMap::new();
+    for (split, color) in parse_split_values(key, value)? {
+        let color = match u32::from_str_radix(color.trim_start_matches('#'), 16) {
+            Ok(color) if color <= 0xffffff => color,
+            _ => {
+                return Err(format!(
+                    "failed to parse color: '{}' for setting: '{}', expected format '#rrggbb'.",
+                    color, key
+                )
+                .into())
+            }
+        };
+        split_colors.insert(split, color);
+    }
+    Ok(split_colors)
+}
+
+pub fn parse_split_thumbnails(
+    key: &str,
+    value: &str,
+) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let split_thumbnails = parse_split_values(key, value)?;
+    for thumbnail in split_thumbnails.values() {
+        if !thumbnail.starts_with("https://") && !thumbnail.starts_with("http://") {
+            return Err(format!(
+                "failed to parse thumbnail: '{}' for setting: '{}', expected an image url.",
+                thumbnail, key
+            )
+            .into());
+        }
+    }
+    Ok(split_thumbnails)
+}
+
+pub fn parse_custom_emojis(
+    key: &str,
+    value: &str,
+) -> Result<HashMap<String, String>, Box<dyn Error>> {
+    let mut emojis = HashMap::new();
+    if value.trim().to_lowercase() == "off" {
+        return Ok(emojis);
+    }
+    for emoji in value.split(',') {
+        let (name, emoji) = match emoji.split_once('=') {
+            Some((name, emoji)) => (name.trim().to_lowercase(), emoji.trim()),
+            None => {
+                return Err(format!(
+                    "failed to parse: '{}' for setting: '{}', expected format 'name=emoji'.",
+                    emoji, key
+                )
+                .into())
+            }
+        };
+        if !EMBED_EMOJIS
+            .iter()
+            .any(|(emoji_name, _)| *emoji_name == name)
+        {
+            return Err(format!(
+                "failed to parse emoji name: '{}' for setting: '{}', expected one of {}.",
+                name,
+                key,
+                EMBED_EMOJIS
+                    .iter()
+                    .map(|(emoji_name, _)| *emoji_name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .into());
+        }
+        emojis.insert(name, emoji.to_string());
+    }
+    Ok(emojis)
+}
+
+pub fn parse_utc_offset(key: &str, value: &str) -> Result<i16, Box<dyn Error>> {
+    let offset = value
+        .trim()
+        .to_uppercase()
+        .replace("UTC", "")
+        .replace(" ", "");
+    if offset.is_empty() {
+        return Ok(0);
+    }
+    let (sign, offset) = match offset.strip_prefix('-') {
+        Some(offset) => (-1, offset),
+        None => (1, offset.strip_prefix('+').unwrap_or(offset.as_str())),
+    };
+    let (hours, minutes) = match offset.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None => (offset, "0"),
+    };
+    match (hours.parse::<i16>(), minutes.parse::<i16>()) {
+        (Ok(hours), Ok(minutes)) if hours <= 14 && minutes < 60 => {
+            Ok(sign * (hours * 60 + minutes))
+        }
+        _ => Err(format!(
+            "failed to parse value: '{}' for setting: '{}', expected an offset like '+2' or '-5:30'.",
+            value, key
+        )
+        .into()),
+    }
+}
+
+pub fn parse_ping_schedule(key: &str, value: &str) -> Result<Vec<PingWindow>, Box<dyn Error>> {
+    let mut windows = Vec::new();
+    if value.trim().to_lowercase() == "off" {
+        return Ok(windows);
+    }
+    for window in value.split(',') {
+        let window = window.trim().to_lowercase();
+        let (day, hours) = match window.split_once(' ') {
+            Some(tup) => tup,
+            None => {
+                return Err(format!(
+                "failed to parse window: '{}' for setting: '{}', expected format 'day start-end'.",
+                window, key
+            )
+                .into())
+            }
+        };
+        let weekday = match day {
+            "daily" => None,
+            day => match WEEKDAYS.iter().position(|weekday| *weekday == day) {
+                Some(weekday) => Some(weekday as u8),
+                None => {
+                    return Err(format!(
+                    "failed to parse day: '{}' for setting: '{}', expected 'daily' or one of {}.",
+                    day,
+                    key,
+                    WEEKDAYS.join(", ")
+                )
+                    .into())
+                }
+            },
+        };
+        let (start, end) = parse_quiet_hours(hours)?;
+        windows.push(PingWindow {
+            weekday,
+            start,
+            end,
+        });
+    }
+    Ok(windows)
+}
+
+pub fn extract_subscription_from_config_line(
+    line: &str,
+) -> Result<SubscriptionCacheEntry, Box<dyn Error>> {
+    let line = line.trim().replace(" ", "");
+    let (user_id, subscription) = match line.split_once(':') {
+        Some(tup) => tup,
+        None => return Err(format!("failed to parse subscription line: '{}'.", line).into()),
+    };
+    let user_id = UserId(user_id.parse::<u64>()?);
+    let parts = subscription.split('/').collect::<Vec<&str>>();
+    if parts.len() < 4 || parts.len() > 6 {
+        return Err(format!("failed to parse subscription line: '{}'.", line).into());
+    }
+    let split = match Split::from_str(parts[1]) {
+        Some(split) => split,
+        None => {
+            return Err(format!(
+                "failed to parse split: '{}' in subscription line: '{}'.",
+                parts[1], line
+            )
+            .into())
+        }
+    };
+    let delivery = match parts.get(4) {
+        Some(delivery) => match SubscriptionDelivery::from_str(delivery) {
+            Some(delivery) => delivery,
+            None => {
+                return Err(format!(
+                    "failed to parse delivery: '{}' in subscription line: '{}'.",
+                    delivery, line
+                )
+                .into())
+            }
+        },
+        None => SubscriptionDelivery::Mention,
+    };
+    let quiet_hours = match parts.get(5) {
+        Some(quiet_hours) => Some(parse_quiet_hours(quiet_hours)?),
+        None => None,
+    };
+    Ok(SubscriptionCacheEntry {
+        user_id,
+        runner: parts[0].to_string(),
+        split,
+        minutes: parts[2].parse::<u8>()?,
+        seconds: parts[3].parse::<u8>()?,
+        delivery,
+        quiet_hours,
+    })
+}
+
+pub fn parse_quiet_hours(quiet_hours: &str) -> Result<(u8, u8), Box<dyn Error>> {
+    let quiet_hours = quiet_hours.trim().replace(" ", "");
+    let (start, end) = match quiet_hours.split_once('-') {
+        Some(tup) => tup,
+        None => {
+            return Err(format!(
+                "failed to parse quiet hours: '{}', expected format 'start-end'.",
+                quiet_hours
+            )
+            .into())
+        }
+    };
+    let mut hours = Vec::new();
+    for hour in [start, end] {
+        match hour.parse::<u8>() {
+            Ok(hour) if hour < 24 => hours.push(hour),
+            _ => {
+                return Err(format!(
+                    "failed to parse quiet hours: '{}', hours must be between 0 and 23.",
+                    quiet_hours
+                )
+                .into())
+            }
+        }
+    }
+    // The end is exclusive, so a window that starts and ends at the same hour would be empty.
+    if hours[0] == hours[1] {
+        return Err(format!(
+            "failed to parse quiet hours: '{}', start and end must be different hours.",
+            quiet_hours
+        )
+        .into());
+    }
+    Ok((hours[0], hours[1]))
+}
+
+pub fn parse_time_threshold(time: &str) -> Result<(u8, u8), Box<dyn Error>> {
+    let time = time.trim();
+    let (minutes, seconds) = match time.split_once(':') {
+        Some((minutes, seconds)) => (minutes, seconds),
+        None => (time, "0"),
+    };
+    let minutes = match minutes.parse::<u8>() {
+        Ok(minutes) => minutes,
+        Err(err) => return Err(format!("failed to parse time: '{}' due to: {}", time, err).into()),
+    };
+    let seconds = match seconds.parse::<u8>() {
+        Ok(seconds) if seconds < 60 => seconds,
+        Ok(_) => {
+            return Err(format!(
+                "failed to parse time: '{}', seconds must be below 60.",
+                time
+            )
+            .into())
+        }
+        Err(err) => return Err(format!("failed to parse time: '{}' due to: {}", time, err).into()),
+    };
+    Ok((minutes, seconds))
+}
+
+pub fn parse_role_messages(key: &str, value: &str) -> Result<Vec<RoleMessage>, Box<dyn Error>> {
+    let mut role_messages = Vec::new();
+    if value.trim().to_lowercase() == "off" {
+        return Ok(role_messages);
+    }
+    for role_message in value.split(',') {
+        let role_message = role_message.trim();
+        if role_message.is_empty() {
+            continue;
+        }
+        let (channel_id, message_ids) = match role_message.split_once(':') {
+            Some(tup) => tup,
+            None => {
+                return Err(format!(
+                    "failed to parse role message: '{}' for setting: '{}', expected format 'channel_id:message_id+message_id'.",
+                    role_message, key
+                )
+                .into())
+            }
+        };
+        let channel_id = ChannelId(channel_id.trim().parse::<u64>()?);
+        let mut parsed_message_ids = Vec::new();
+        for message_id in message_ids.split('+') {
+            parsed_message_ids.push(MessageId(message_id.trim().parse::<u64>()?));
+        }
+        role_messages.push(RoleMessage {
+            channel_id,
+            message_ids: parsed_message_ids,
+        });
+    }
+    Ok(role_messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_lines() {
+        let subscription = extract_subscription_from_config_line("123:Runner/B/4/30").unwrap();
+        assert_eq!(subscription.user_id, UserId(123));
+        assert_eq!(subscription.runner, "Runner");
+        assert_eq!(subscription.split, Split::Blind);
+        assert_eq!((subscription.minutes, subscription.seconds), (4, 30));
+
+        assert!(extract_subscription_from_config_line("123:Runner/X/4/30").is_err());
+        assert!(extract_subscription_from_config_line("123:Runner/B/4").is_err());
+    }
+
+    #[test]
+    fn quiet_hours() {
+        assert_eq!(parse_quiet_hours("23-8").unwrap(), (23, 8));
+        assert_eq!(parse_quiet_hours(" 1 - 5 ").unwrap(), (1, 5));
+        assert!(parse_quiet_hours("22-22").is_err());
+        assert!(parse_quiet_hours("24-1").is_err());
+        assert!(parse_quiet_hours("5").is_err());
+    }
+
+    #[test]
+    fn subscription_delivery_and_quiet_hours() {
+        let subscription = extract_subscription_from_config_line("123:Runner/B/4/30").unwrap();
+        assert!(subscription.delivery == SubscriptionDelivery::Mention);
+        assert_eq!(subscription.quiet_hours, None);
+
+        let line = "123:Runner/EE/7/0/dm/22-7";
+        let subscription = extract_subscription_from_config_line(line).unwrap();
+        assert!(subscription.delivery == SubscriptionDelivery::Dm);
+        assert_eq!(subscription.quiet_hours, Some((22, 7)));
+        assert_eq!(subscription.to_config_line(), line);
+        assert!(subscription.is_quiet(23) && subscription.is_quiet(6));
+        assert!(!subscription.is_quiet(7) && !subscription.is_quiet(21));
+
+        assert!(extract_subscription_from_config_line("123:Runner/B/4/30/sms").is_err());
+        assert!(extract_subscription_from_config_line("123:Runner/B/4/30/dm/7-7").is_err());
+    }
+
+    #[test]
+    fn utc_offsets() {
+        assert_eq!(parse_utc_offset("utc_offset", "+2").unwrap(), 120);
+        assert_eq!(parse_utc_offset("utc_offset", "-5:30").unwrap(), -330);
+        assert_eq!(parse_utc_offset("utc_offset", "UTC+5:45").unwrap(), 345);
+        assert_eq!(parse_utc_offset("utc_offset", "").unwrap(), 0);
+        assert!(parse_utc_offset("utc_offset", "+15").is_err());
+        assert!(parse_utc_offset("utc_offset", "+2:60").is_err());
+        assert!(parse_utc_offset("utc_offset", "two").is_err());
+    }
+
+    #[test]
+    fn ping_schedule() {
+        assert!(parse_ping_schedule("ping_schedule", "off")
+            .unwrap()
+            .is_empty());
+        let windows = parse_ping_schedule("ping_schedule", "daily 18-22, Sat 22-2").unwrap();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(
+            (windows[0].weekday, windows[0].start, windows[0].end),
+            (None, 18, 22)
+        );
+        assert_eq!(
+            (windows[1].weekday, windows[1].start, windows[1].end),
+            (Some(5), 22, 2)
+        );
+        assert!(parse_ping_schedule("ping_schedule", "someday 1-2").is_err());
+        assert!(parse_ping_schedule("ping_schedule", "daily").is_err());
+        assert!(parse_ping_schedule("ping_schedule", "daily 3-3").is_err());
+    }
+
+    #[test]
+    fn role_messages() {
+        assert!(parse_role_messages("role_messages", "off")
+            .unwrap()
+            .is_empty());
+        let role_messages = parse_role_messages("role_messages", "1:2+3, 4:5").unwrap();
+        assert_eq!(
+            role_messages
+                .iter()
+                .map(|role_message| role_message.to_str())
+                .collect::<Vec<_>>(),
+            vec!["1:2+3", "4:5"]
+        );
+        assert!(parse_role_messages("role_messages", "1").is_err());
+        assert!(parse_role_messages("role_messages", "1:x").is_err());
+    }
+}
@@ -0,0 +1,297 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{cache::PlayerCacheEntry, config::extract_name_or_uuid_and_splits_from_config_line};
+
+pub const WHITELIST_CSV_HEADER: &str =
+    "runner,first_structure,second_structure,blind,eye_spy,end_enter,finish,display_name,uuid";
+
+#[derive(Serialize, Deserialize)]
+pub struct WhitelistRecord {
+    pub runner: String,
+    #[serde(default)]
+    pub first_structure: u64,
+    #[serde(default)]
+    pub second_structure: u64,
+    #[serde(default)]
+    pub blind: u64,
+    #[serde(default)]
+    pub eye_spy: u64,
+    #[serde(default)]
+    pub end_enter: u64,
+    #[serde(default)]
+    pub finish: Option<u64>,
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub uuid: Option<String>,
+}
+
+impl WhitelistRecord {
+    pub fn from_player(runner: &str, player: &PlayerCacheEntry) -> Self {
+        Self {
+            runner: runner.to_string(),
+            first_structure: player.first_structure as u64,
+            second_structure: player.second_structure as u64,
+            blind: player.blind as u64,
+            eye_spy: player.eye_spy as u64,
+            end_enter: player.end_enter as u64,
+            finish: player.finish.map(|finish| finish as u64),
+            display_name: player.display_name.to_owned(),
+            uuid: player.uuid.to_owned(),
+        }
+    }
+
+    // Goes through the config line format, so that imported runners are validated
+    // exactly like the ones in #pacemanbot-runner-names.
+    pub fn to_config_line(&self) -> String {
+        let finish = match self.finish {
+            Some(finish) => format!("/{}", finish),
+            None => "".to_string(),
+        };
+        let display_name = match self.display_name.as_ref() {
+            Some(display_name) => format!("|{}", display_name),
+            None => "".to_string(),
+        };
+        let runner = match self.uuid.as_ref() {
+            Some(uuid) => format!("{}@{}", self.runner, uuid),
+            None => self.runner.to_owned(),
+        };
+        format!(
+            "{}:{}/{}/{}/{}/{}{}{}",
+            runner,
+            self.first_structure,
+            self.second_structure,
+            self.blind,
+            self.eye_spy,
+            self.end_enter,
+            finish,
+            display_name
+        )
+    }
+}
+
+pub fn parse_whitelist_file(
+    file_name: &str,
+    contents: &str,
+) -> Result<HashMap<String, PlayerCacheEntry>, Box<dyn Error>> {
+    let records = if file_name.to_lowercase().ends_with(".json") {
+        serde_json::from_str::<Vec<WhitelistRecord>>(contents)
+            .map_err(|err| format!("failed to parse json: {}", err))?
+    } else {
+        parse_whitelist_csv(contents)?
+    };
+    let mut players = HashMap::new();
+    let mut runners = HashSet::new();
+    let mut errors = Vec::new();
+    for (idx, record) in records.iter().enumerate() {
+        let line = record.to_config_line();
+        match extract_name_or_uuid_and_splits_from_config_line(line.as_str()) {
+            Ok((runner, player)) => {
+                // Runners are looked up case insensitively.
+                if !runners.insert(runner.to_lowercase()) {
+                    errors.push(format!(
+                        "entry {}: duplicate runner: '{}'.",
+                        idx + 1,
+                        runner
+                    ));
+                }
+                players.insert(runner, player);
+            }
+            Err(err) => errors.push(format!("entry {}: {}", idx + 1, err)),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(format!("failed to validate file:\n{}", errors.join("\n")).into());
+    }
+    Ok(players)
+}
+
+fn parse_whitelist_csv(contents: &str) -> Result<Vec<WhitelistRecord>, Box<dyn Error>> {
+    let mut records = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("runner,") {
+            continue;
+        }
+        let fields = split_csv_line(line);
+        if fields.len() < 6 || fields.len() > 9 {
+            return Err(format!(
+                "failed to parse line {}: '{}', expected the columns: {}.",
+                idx + 1,
+                line,
+                WHITELIST_CSV_HEADER
+            )
+            .into());
+        }
+        let mut splits = Vec::new();
+        for field in fields[1..6].iter() {
+            splits.push(match field.trim() {
+                "" => 0,
+                field => field
+                    .parse::<u64>()
+                    .map_err(|err| format!("failed to parse line {}: {}", idx + 1, err))?,
+            });
+        }
+        let finish = match fields.get(6).map(|field| field.trim()) {
+            None | Some("") => None,
+            Some(field) => Some(
+                field
+                    .parse::<u64>()
+                    .map_err(|err| format!("failed to parse line {}: {}", idx + 1, err))?,
+            ),
+        };
+        records.push(WhitelistRecord {
+            runner: fields[0].trim().to_string(),
+            first_structure: splits[0],
+            second_structure: splits[1],
+            blind: splits[2],
+            eye_spy: splits[3],
+            end_enter: splits[4],
+            finish,
+            display_name: fields
+                .get(7)
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty()),
+            uuid: fields
+                .get(8)
+                .map(|field| field.trim().to_string())
+                .filter(|field| !field.is_empty()),
+        });
+    }
+    Ok(records)
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn escape_csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+pub fn whitelist_to_csv(players: &HashMap<String, PlayerCacheEntry>) -> String {
+    let mut lines = vec![WHITELIST_CSV_HEADER.to_string()];
+    for record in whitelist_to_records(players) {
+        lines.push(format!(
+            "{},{},{},{},{},{},{},{},{}",
+            escape_csv_field(record.runner.as_str()),
+            record.first_structure,
+            record.second_structure,
+            record.blind,
+            record.eye_spy,
+            record.end_enter,
+            record
+                .finish
+                .map(|finish| finish.to_string())
+                .unwrap_or_default(),
+            escape_csv_field(record.display_name.unwrap_or_default().as_str()),
+            record.uuid.unwrap_or_default()
+        ));
+    }
+    lines.join("\n")
+}
+
+pub fn whitelist_to_json(
+    players: &HashMap<String, PlayerCacheEntry>,
+) -> Result<String, Box<dyn Error>> {
+    Ok(serde_json::to_string_pretty(&whitelist_to_records(
+        players,
+    ))?)
+}
+
+fn whitelist_to_records(players: &HashMap<String, PlayerCacheEntry>) -> Vec<WhitelistRecord> {
+    let mut runners = players.keys().collect::<Vec<_>>();
+    runners.sort_by_key(|runner| runner.to_lowercase());
+    runners
+        .into_iter()
+        .map(|runner| WhitelistRecord::from_player(runner, &players[runner]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_quoted_fields() {
+        let contents = format!(
+            "{}\nIts_Saanvi,5,7,9,11,13,,\"Saanvi, \"\"the\"\" runner\"\n",
+            WHITELIST_CSV_HEADER
+        );
+        let players = parse_whitelist_file("whitelist.csv", contents.as_str()).unwrap();
+        let player = &players["Its_Saanvi"];
+        assert_eq!(player.first_structure, 5);
+        assert_eq!(player.end_enter, 13);
+        assert_eq!(player.finish, None);
+        assert_eq!(
+            player.display_name.as_deref(),
+            Some("Saanvi, \"the\" runner")
+        );
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let contents = "runner_a,1,2,3,4,5,10,\"a, b\"\nrunner_b,0,0,8,0,0";
+        let players = parse_whitelist_file("whitelist.csv", contents).unwrap();
+        let exported = whitelist_to_csv(&players);
+        let reimported = parse_whitelist_file("export.csv", exported.as_str()).unwrap();
+        assert_eq!(whitelist_to_csv(&reimported), exported);
+        assert_eq!(reimported["runner_a"].display_name.as_deref(), Some("a, b"));
+    }
+
+    #[test]
+    fn duplicate_runners() {
+        let contents = "runner_a,1,2,3,4,5\nRunner_A,5,4,3,2,1";
+        let err = parse_whitelist_file("whitelist.csv", contents)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("entry 2: duplicate runner"));
+    }
+
+    #[test]
+    fn invalid_split_rows() {
+        assert!(parse_whitelist_file("whitelist.csv", "runner_a,1,2,three,4,5").is_err());
+        assert!(parse_whitelist_file("whitelist.csv", "runner_a,1,2,3").is_err());
+        assert!(
+            parse_whitelist_file("whitelist.csv", "runner_a,1,2,3,4,5,6,name,uuid,extra").is_err()
+        );
+    }
+
+    #[test]
+    fn json_files() {
+        let contents = r#"[{"runner": "runner_a", "blind": 8, "finish": 12}]"#;
+        let players = parse_whitelist_file("whitelist.JSON", contents).unwrap();
+        assert_eq!(players["runner_a"].blind, 8);
+        assert_eq!(players["runner_a"].finish, Some(12));
+        let err = parse_whitelist_file("whitelist.json", r#"[{"runner": "runner_a""#)
+            .err()
+            .unwrap();
+        assert!(err.to_string().starts_with("failed to parse json"));
+        assert!(parse_whitelist_file("whitelist.json", r#"[{"blind": 8}]"#).is_err());
+    }
+}
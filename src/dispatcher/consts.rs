@@ -1,11 +1,28 @@
-pub const SPECIAL_UNDERSCORE: &'static str = "ˍ";
-pub const STATS_URL_PREFIX: &'static str = "https://paceman.gg/stats/run/";
-pub const MC_HEAD_URL_PREFIX: &'static str = "https://api.mineatar.io/face/";
-pub const TWITCH_LINK_PREFIX: &'static str = "https://twitch.tv/";
-pub const LIVE_INDICATOR: &'static str = "🔴";
-pub const OFFLINE_INDICATOR: &'static str = "⚪";
-pub const PEARL_EMOJI: &'static str = "<:pearl:1306295843766403133>";
-pub const ROD_EMOJI: &'static str = "<:blazerod:1306297336070406196>";
-pub const CREDITS_EMOJI: &'static str = "<:credits:1306295962578321471>";
-pub const TWITCH_EMOJI: &'static str = "<:twitch:1306295796932808804>";
-pub const OFFLINE_EMOJI: &'static str = "<:offline:1306296099312631858>";
+use serenity::utils::Colour;
+
+pub const SPECIAL_UNDERSCORE: &str = "ˍ";
+pub const STATS_URL_PREFIX: &str = "https://paceman.gg/stats/run/";
+pub const PACEMAN_PROFILE_URL_PREFIX: &str = "https://paceman.gg/stats/player/";
+pub const MC_HEAD_URL_PREFIX: &str = "https://api.mineatar.io/face/";
+pub const TWITCH_LINK_PREFIX: &str = "https://twitch.tv/";
+pub const YOUTUBE_LINK_PREFIX: &str = "https://youtube.com/@";
+pub const KICK_LINK_PREFIX: &str = "https://kick.com/";
+pub const LIVE_INDICATOR: &str = "🔴";
+pub const OFFLINE_INDICATOR: &str = "⚪";
+pub const WARNING_INDICATOR: &str = "⚠️";
+pub const PEARL_EMOJI: &str = "<:pearl:1306295843766403133>";
+pub const ROD_EMOJI: &str = "<:blazerod:1306297336070406196>";
+pub const CREDITS_EMOJI: &str = "<:credits:1306295962578321471>";
+pub const TWITCH_EMOJI: &str = "<:twitch:1306295796932808804>";
+pub const YOUTUBE_EMOJI: &str = "▶️";
+pub const KICK_EMOJI: &str = "🟩";
+pub const OFFLINE_EMOJI: &str = "<:offline:1306296099312631858>";
+pub const RUN_ENDED_COLOR: u32 = Colour::from_rgb(128, 132, 142).0;
+pub const PACE_GREAT_COLOR: (u8, u8, u8) = (46, 204, 113);
+pub const PACE_BORDERLINE_COLOR: (u8, u8, u8) = (241, 196, 15);
+// (average split to finish time, margin) in milliseconds, used when a runner has no history.
+pub const COMMUNITY_FS_FINISH_DELTA: (u64, u64) = (450000, 90000);
+pub const COMMUNITY_SS_FINISH_DELTA: (u64, u64) = (330000, 75000);
+pub const COMMUNITY_BLIND_FINISH_DELTA: (u64, u64) = (240000, 60000);
+pub const COMMUNITY_EYE_SPY_FINISH_DELTA: (u64, u64) = (150000, 45000);
+pub const COMMUNITY_END_ENTER_FINISH_DELTA: (u64, u64) = (90000, 30000);
@@ -1,22 +1,39 @@
-use std::{collections::HashMap, error::Error, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    error::Error,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use serenity::{
-    builder::CreateEmbedAuthor, client::Context, futures::lock::Mutex, model::id::ChannelId,
+    builder::{CreateEmbed, CreateEmbedAuthor},
+    client::Context,
+    futures::lock::Mutex,
+    model::{
+        channel::Message,
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+    },
     prelude::Mentionable,
 };
 use tokio::time::sleep;
 
 use crate::{
-    cache::{Cache, GuildCacheEntry, PlayerCacheEntry, RoleCacheEntry, EDIT_MESSAGE_DELAY},
-    config::PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL,
+    cache::{
+        AuthorLink, Cache, CheatedRunPolicy, GuildCacheEntry, GuildSettings, PingStripMode,
+        PlayerCacheEntry, RunCacheEntry, SubscriptionCacheEntry, SubscriptionDelivery,
+        DM_FAILURE_LIMIT, PACE_HISTORY_MAX_RUNNERS, PROCESSED_EVENT_TTL,
+    },
+    command::{get_config_message, set_config_message_contents},
+    config::{PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL, PACEMANBOT_SUBSCRIPTIONS_CHANNEL},
     dispatcher::{
-        format_time, millis_to_mins_secs, mins_secs_to_millis, EventType, RunInfo, RunType,
-        CREDITS_EMOJI, LIVE_INDICATOR, MC_HEAD_URL_PREFIX, OFFLINE_EMOJI, OFFLINE_INDICATOR,
-        PEARL_EMOJI, ROD_EMOJI, SPECIAL_UNDERSCORE, STATS_URL_PREFIX, TWITCH_EMOJI,
-        TWITCH_LINK_PREFIX,
+        current_timestamp, escape_markdown, format_time, millis_to_mins_secs, mins_secs_to_millis,
+        pace_quality_color, EventType, GuildEvent, PaceEmbed, PaceMessage, Projection, RunInfo,
+        RunType, CREDITS_EMOJI, LIVE_INDICATOR, MC_HEAD_URL_PREFIX, OFFLINE_INDICATOR,
+        PACEMAN_PROFILE_URL_PREFIX, PEARL_EMOJI, ROD_EMOJI, RUN_ENDED_COLOR, STATS_URL_PREFIX,
     },
+    locale::Locale,
     log::Log,
-    ws::{Event, ItemData, WSResponse},
+    ws::{Event, ItemData, LiveAccount, WSResponse},
 };
 
 pub struct Dispatcher {
@@ -37,10 +54,10 @@ impl Dispatcher {
     ) -> Self {
         let stats_link = format!("{}{}", STATS_URL_PREFIX, ws_response.world_id);
         let mc_head_url = format!("{}{}", MC_HEAD_URL_PREFIX, ws_response.user.uuid);
-        let author_name = ws_response.nickname.replace("_", SPECIAL_UNDERSCORE);
         let mut partial_author = CreateEmbedAuthor::default();
         partial_author.icon_url(mc_head_url);
-        partial_author.name(author_name);
+        // Embed author names aren't rendered as markdown, so they aren't escaped.
+        partial_author.name(ws_response.nickname.to_owned());
         Self {
             ctx,
             log,
@@ -50,6 +67,39 @@ impl Dispatcher {
             partial_author,
         }
     }
+
+    fn get_display_name(&self, guild_cache_entry: &GuildCacheEntry) -> String {
+        let player_data = guild_cache_entry
+            .find_runner(&self.ws_response.nickname, &self.ws_response.user.uuid)
+            .and_then(|runner_key| guild_cache_entry.player_whitelist.get(&runner_key));
+        match player_data.and_then(|player_data| player_data.display_name.as_ref()) {
+            Some(display_name) => display_name.to_owned(),
+            None => self.ws_response.nickname.to_owned(),
+        }
+    }
+
+    fn get_author(
+        &self,
+        guild_cache_entry: &GuildCacheEntry,
+        live_account: Option<&LiveAccount>,
+    ) -> CreateEmbedAuthor {
+        let mut author = self.partial_author.clone();
+        author.name(self.get_display_name(guild_cache_entry));
+        let author_link = match guild_cache_entry.settings.author_link {
+            AuthorLink::Stream => live_account.map(|live_account| live_account.link()),
+            AuthorLink::Profile => Some(format!(
+                "{}{}",
+                PACEMAN_PROFILE_URL_PREFIX, self.ws_response.nickname
+            )),
+            AuthorLink::Run => Some(self.stats_link.to_owned()),
+            AuthorLink::Off => None,
+        };
+        if let Some(author_link) = author_link {
+            author.url(author_link);
+        }
+        author
+    }
+
     pub async fn dispatch(&self) -> Result<(), Box<dyn Error>> {
         let game_version = self.ws_response.game_version.to_owned();
         if game_version.is_some() && game_version.unwrap() != "1.16.1" {
@@ -67,40 +117,165 @@ impl Dispatcher {
                 .into())
             }
         };
+        let event_type = EventType::from(last_event);
         let mut locked_cache = self.cache.lock().await;
-        for (guild_id, guild_cache_entry) in locked_cache.entries.iter_mut() {
-            let live_link = match self.ws_response.user.live_account.to_owned() {
-                Some(live_account) => format!("{}{}", TWITCH_LINK_PREFIX, live_account),
-                None => {
-                    if !match GuildCacheEntry::is_private(
-                        guild_cache_entry.name.to_string(),
-                        self.ctx.clone(),
-                        guild_id,
-                    ) {
-                        Ok(is_private) => is_private,
-                        Err(err) => {
-                            self.log.warn(err.to_string().as_str());
-                            continue;
-                        }
-                    } {
-                        self.log.warn(
-                            format!(
-                                "Skipping guild: '{}' because user with name: '{}' is not live.",
-                                guild_cache_entry.name, self.ws_response.nickname,
-                            )
-                            .as_str(),
-                        );
+        let cache = &mut *locked_cache;
+        cache.processed_events.retain(|_, processed_at| {
+            processed_at.elapsed() < Duration::from_secs(PROCESSED_EVENT_TTL)
+        });
+        let event_key = (
+            self.ws_response.world_id.to_owned(),
+            last_event.event_id,
+            last_event.igt,
+        );
+        if cache.processed_events.contains_key(&event_key) {
+            self.log.info(
+                format!(
+                    "Skipping duplicate event: {:#?} at {} for world id: '{}'.",
+                    last_event.event_id,
+                    format_time(last_event.igt as u64),
+                    self.ws_response.world_id,
+                )
+                .as_str(),
+            );
+            return Ok(());
+        }
+        cache.processed_events.insert(event_key, Instant::now());
+        cache.runs.retain(|_, run| !run.is_stale());
+        let run = cache
+            .runs
+            .entry(self.ws_response.world_id.to_owned())
+            .or_insert_with(|| RunCacheEntry::new(self.ws_response.nickname.to_owned()));
+        if run.is_out_of_order(last_event) {
+            self.log.warn(
+                format!(
+                    "Skipping out of order event: {:#?} at {} for world id: '{}'.",
+                    last_event.event_id,
+                    format_time(last_event.igt as u64),
+                    self.ws_response.world_id,
+                )
+                .as_str(),
+            );
+            return Ok(());
+        }
+        let was_ended = run.is_ended;
+        run.update(last_event);
+        if run.is_ended && !was_ended && !run.is_finished {
+            self.log.info(
+                format!(
+                    "Run with world id: '{}' for user with name: '{}' was reset or abandoned.",
+                    self.ws_response.world_id, run.nickname,
+                )
+                .as_str(),
+            );
+        }
+        let cheat_flags = self.ws_response.cheat_flags();
+        let mut projection = None;
+        match event_type {
+            EventType::PaceEvent => {
+                if let Some(run_info) = RunInfo::from_last_event(
+                    last_event,
+                    self.ws_response.event_list.to_vec(),
+                    self.ws_response.context_event_list.to_vec(),
+                ) {
+                    projection = Some(Projection::estimate(
+                        &run_info.split,
+                        last_event.igt as u64,
+                        cache.pace_history.get(&self.ws_response.user.uuid),
+                    ));
+                }
+            }
+            EventType::NonPaceEvent if cheat_flags.is_empty() => {
+                if !cache.pace_history.contains_key(&self.ws_response.user.uuid)
+                    && cache.pace_history.len() >= PACE_HISTORY_MAX_RUNNERS
+                {
+                    let oldest = cache
+                        .pace_history
+                        .iter()
+                        .min_by_key(|(_, pace_history)| pace_history.last_recorded)
+                        .map(|(uuid, _)| uuid.to_owned());
+                    if let Some(oldest) = oldest {
+                        cache.pace_history.remove(&oldest);
+                    }
+                }
+                let pace_history = cache
+                    .pace_history
+                    .entry(self.ws_response.user.uuid.to_owned())
+                    .or_default();
+                for (split, igt) in RunInfo::split_times(&self.ws_response.event_list) {
+                    pace_history.record(split, (last_event.igt as u64).saturating_sub(igt));
+                }
+            }
+            _ => (),
+        };
+        if let EventType::RunEndEvent = event_type {
+            if was_ended || !run.is_ended || run.is_finished {
+                return Ok(());
+            }
+            for (guild_id, guild_cache_entry) in cache.entries.iter() {
+                if !guild_cache_entry.settings.run_end_notifications {
+                    continue;
+                }
+                if !cheat_flags.is_empty()
+                    && guild_cache_entry.settings.cheated_run_policy == CheatedRunPolicy::Suppress
+                {
+                    continue;
+                }
+                let run_messages = match run.messages.get(guild_id) {
+                    Some(run_messages) => run_messages,
+                    None => continue,
+                };
+                match self
+                    .handle_run_end_event(guild_cache_entry, run_messages)
+                    .await
+                {
+                    Ok(_) => (),
+                    Err(err) => self.log.warn(err.to_string().as_str()),
+                };
+            }
+            return Ok(());
+        }
+        for (guild_id, guild_cache_entry) in cache.entries.iter_mut() {
+            if !cheat_flags.is_empty()
+                && guild_cache_entry.settings.cheated_run_policy == CheatedRunPolicy::Suppress
+            {
+                self.log.warn(
+                    format!(
+                        "Skipping guild: '{}' because run with world id: '{}' is flagged: {}.",
+                        guild_cache_entry.name,
+                        self.ws_response.world_id,
+                        cheat_flags.join(", "),
+                    )
+                    .as_str(),
+                );
+                continue;
+            }
+            let live_account = self
+                .ws_response
+                .user
+                .live_account_on(&guild_cache_entry.settings.live_platforms);
+            if live_account.is_none()
+                && !match GuildCacheEntry::is_private(
+                    guild_cache_entry.name.to_string(),
+                    self.ctx.clone(),
+                    guild_id,
+                ) {
+                    Ok(is_private) => is_private,
+                    Err(err) => {
+                        self.log.warn(err.to_string().as_str());
                         continue;
                     }
-                    "".to_string()
                 }
-            };
-            let mut author = self.partial_author.clone();
-            if live_link.is_empty() {
-                author.url(live_link.clone());
+            {
+                self.log.warn(
+                    format!(
+                        "Skipping guild: '{}' because user with name: '{}' is not live.",
+                        guild_cache_entry.name, self.ws_response.nickname,
+                    )
+                    .as_str(),
+                );
+                continue;
             }
-
-            let event_type = EventType::from(last_event);
             let is_private = match GuildCacheEntry::is_private(
                 guild_cache_entry.name.to_string(),
                 self.ctx.clone(),
@@ -109,31 +284,43 @@ impl Dispatcher {
                 Ok(is_private) => is_private,
                 Err(err) => return Err(err.into()),
             };
-            let has_player_ign = guild_cache_entry
-                .player_whitelist
-                .iter()
-                .any(|p| p.0 == &self.ws_response.nickname.to_lowercase());
-            let has_player_uuid = guild_cache_entry
-                .player_whitelist
-                .iter()
-                .any(|p| p.0 == &self.ws_response.user.uuid);
-            if !has_player_ign && !has_player_uuid {
-                if is_private {
-                    self.log.warn(format!(
-                        "Skipping guild because player name: {} is not in the runners channel for guild name: {}", 
-                        self.ws_response.nickname,
-                        guild_cache_entry.name
-                    ).as_str());
-                    continue;
+            let runner_key = match guild_cache_entry
+                .find_runner(&self.ws_response.nickname, &self.ws_response.user.uuid)
+            {
+                Some(runner_key) => runner_key,
+                None => {
+                    if is_private {
+                        self.log.warn(format!(
+                            "Skipping guild because player name: {} is not in the runners channel for guild name: {}",
+                            self.ws_response.nickname,
+                            guild_cache_entry.name
+                        ).as_str());
+                        continue;
+                    }
+                    let runner_key = self.ws_response.nickname.to_lowercase();
+                    guild_cache_entry
+                        .player_whitelist
+                        .insert(runner_key.to_owned(), PlayerCacheEntry::default());
+                    runner_key
                 }
-                let player_data = PlayerCacheEntry::default();
-                guild_cache_entry.player_whitelist.insert(
-                    self.ws_response.nickname.to_owned().to_lowercase(),
-                    player_data,
-                );
-            }
-            match event_type {
-                EventType::Unknown => {
+            };
+            let author = self.get_author(guild_cache_entry, live_account.as_ref());
+            let reply_to = if guild_cache_entry.settings.run_replies {
+                run.last_message(guild_id)
+            } else {
+                None
+            };
+            let guild_event = GuildEvent {
+                live_account: live_account.clone(),
+                author,
+                last_event,
+                is_private,
+                runner_key,
+                projection: projection.as_ref(),
+                reply_to,
+            };
+            let sent_message = match event_type {
+                EventType::RunEndEvent | EventType::Unknown => {
                     self.log.warn(
                         format!(
                             "Unknown event type: {:#?}. Skipping all guilds.",
@@ -145,36 +332,31 @@ impl Dispatcher {
                 }
                 EventType::NonPaceEvent => {
                     match self
-                        .handle_non_pace_event(
-                            live_link,
-                            author,
-                            last_event,
-                            guild_cache_entry,
-                            is_private,
-                            has_player_uuid,
-                        )
+                        .handle_non_pace_event(guild_event, guild_cache_entry)
                         .await
                     {
-                        Ok(_) => (),
-                        Err(err) => self.log.warn(err.to_string().as_str()),
-                    };
+                        Ok(message) => message,
+                        Err(err) => {
+                            self.log.warn(err.to_string().as_str());
+                            None
+                        }
+                    }
                 }
                 EventType::PaceEvent => {
                     match self
-                        .handle_pace_event(
-                            live_link,
-                            author,
-                            last_event,
-                            guild_cache_entry,
-                            is_private,
-                            has_player_uuid,
-                        )
+                        .handle_pace_event(guild_event, guild_id, guild_cache_entry)
                         .await
                     {
-                        Ok(_) => (),
-                        Err(err) => self.log.warn(err.to_string().as_str()),
-                    };
+                        Ok(message) => message,
+                        Err(err) => {
+                            self.log.warn(err.to_string().as_str());
+                            None
+                        }
+                    }
                 }
+            };
+            if let Some(message) = sent_message {
+                run.record_message(guild_id.to_owned(), &message);
             }
         }
         Ok(())
@@ -182,13 +364,19 @@ impl Dispatcher {
 
     pub async fn handle_pace_event(
         &self,
-        live_link: String,
-        author: CreateEmbedAuthor,
-        last_event: &Event,
+        guild_event: GuildEvent<'_>,
+        guild_id: &GuildId,
         guild_cache_entry: &mut GuildCacheEntry,
-        is_private: bool,
-        has_player_uuid: bool,
-    ) -> Result<(), Box<dyn Error>> {
+    ) -> Result<Option<Message>, Box<dyn Error>> {
+        let GuildEvent {
+            live_account,
+            author,
+            last_event,
+            is_private,
+            runner_key,
+            projection,
+            reply_to,
+        } = guild_event;
         let event_list: Vec<Event> = self.ws_response.event_list.iter().cloned().collect();
         let context_event_list: Vec<Event> = self
             .ws_response
@@ -203,16 +391,11 @@ impl Dispatcher {
                 return Err(format!("unrecognized event id: {:#?}.", last_event.event_id).into());
             }
         };
-        let player_data = if has_player_uuid {
-            guild_cache_entry
-                .player_whitelist
-                .get_mut(&self.ws_response.user.uuid)
-                .unwrap()
-        } else {
-            guild_cache_entry
-                .player_whitelist
-                .get_mut(&self.ws_response.nickname.to_lowercase())
-                .unwrap()
+        let player_data = match guild_cache_entry.player_whitelist.get_mut(&runner_key) {
+            Some(player_data) => player_data,
+            None => {
+                return Err(format!("failed to get whitelist entry for: '{}'.", runner_key).into())
+            }
         };
         let split_desc = match run_info.split.desc(&run_info.structure) {
             Some(desc) => desc,
@@ -230,6 +413,11 @@ impl Dispatcher {
                 );
             }
         };
+        let ping_projection = if guild_cache_entry.settings.ping_on_projection {
+            projection
+        } else {
+            None
+        };
         let roles_to_ping = guild_cache_entry
             .roles
             .iter()
@@ -240,65 +428,212 @@ impl Dispatcher {
                     last_event,
                     is_private,
                     &self.ws_response,
+                    ping_projection,
+                )
+            })
+            .collect::<Vec<_>>();
+        let current_hour = guild_cache_entry.settings.local_hour(current_timestamp());
+        let notifiable_subscriptions = guild_cache_entry
+            .subscriptions
+            .iter()
+            .filter(|subscription| {
+                subscription.is_notifiable(
+                    &run_info,
+                    last_event,
+                    &self.ws_response,
+                    ping_projection,
+                    current_hour,
                 )
             })
             .collect::<Vec<_>>();
-        if roles_to_ping.is_empty() {
+        let mut subscribers_to_ping = notifiable_subscriptions
+            .iter()
+            .filter(|subscription| subscription.delivery == SubscriptionDelivery::Mention)
+            .map(|subscription| subscription.user_id)
+            .collect::<Vec<_>>();
+        subscribers_to_ping.sort();
+        subscribers_to_ping.dedup();
+        let mut subscribers_to_dm = notifiable_subscriptions
+            .iter()
+            .filter(|subscription| subscription.delivery == SubscriptionDelivery::Dm)
+            .map(|subscription| subscription.user_id)
+            .collect::<Vec<_>>();
+        subscribers_to_dm.sort();
+        subscribers_to_dm.dedup();
+        if roles_to_ping.is_empty()
+            && subscribers_to_ping.is_empty()
+            && subscribers_to_dm.is_empty()
+        {
             self.log.warn(
                 format!(
-                    "Skipping split: '{}' because there are no roles to ping in guild name: {}.",
+                    "Skipping split: '{}' because there are no roles or subscribers to ping in guild name: {}.",
                     split_desc, guild_cache_entry.name
                 )
                 .as_str(),
             );
-            return Ok(());
+            return Ok(None);
+        }
+        let warning_msg = self.cheat_warning(&guild_cache_entry.settings);
+        let should_post = !roles_to_ping.is_empty() || !subscribers_to_ping.is_empty();
+        let settings = &guild_cache_entry.settings;
+        let ping_state = &guild_cache_entry.ping_state;
+        let is_runner_on_cooldown =
+            ping_state.is_runner_on_cooldown(&self.ws_response.nickname, settings.runner_cooldown);
+        let (roles_to_mention, roles_on_cooldown): (Vec<_>, Vec<_>) =
+            roles_to_ping.into_iter().partition(|role_cache_entry| {
+                !is_runner_on_cooldown
+                    && !ping_state
+                        .is_role_on_cooldown(&role_cache_entry.role.id, settings.role_cooldown)
+            });
+        if !roles_on_cooldown.is_empty() {
+            self.log.info(
+                format!(
+                    "Dropping {} role mention(s) for split: '{}' because of a cooldown in guild name: {}.",
+                    roles_on_cooldown.len(),
+                    split_desc,
+                    guild_cache_entry.name
+                )
+                .as_str(),
+            );
+        }
+        let mut mentioned_role_ids = roles_to_mention
+            .iter()
+            .map(|role_cache_entry| role_cache_entry.role.id)
+            .collect::<Vec<_>>();
+        let mut digest_role_ids = if settings.ping_digest {
+            roles_on_cooldown
+                .iter()
+                .map(|role_cache_entry| role_cache_entry.role.id)
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
+        let mut mentions = roles_to_mention
+            .iter()
+            .map(|role_cache_entry| role_cache_entry.role.mention().to_string())
+            .collect::<Vec<_>>();
+        let mut removable_mentions = roles_to_mention
+            .iter()
+            .filter(|role_cache_entry| !role_cache_entry.runner.is_empty())
+            .map(|role_cache_entry| role_cache_entry.role.mention().to_string())
+            .collect::<Vec<_>>();
+        for user_id in subscribers_to_ping {
+            mentions.push(user_id.mention().to_string());
+            removable_mentions.push(user_id.mention().to_string());
         }
-        let live_indicator = if self.ws_response.user.live_account.is_some() {
+        if warning_msg.is_some()
+            && guild_cache_entry.settings.cheated_run_policy == CheatedRunPolicy::NoPings
+        {
+            mentions.clear();
+            removable_mentions.clear();
+            mentioned_role_ids.clear();
+            digest_role_ids.clear();
+            subscribers_to_dm.clear();
+        }
+        if !guild_cache_entry
+            .settings
+            .is_ping_allowed(current_timestamp())
+        {
+            self.log.info(
+                format!(
+                    "Not pinging for split: '{}' because pings are muted by the schedule in guild name: {}.",
+                    split_desc, guild_cache_entry.name
+                )
+                .as_str(),
+            );
+            mentions.clear();
+            removable_mentions.clear();
+            mentioned_role_ids.clear();
+            digest_role_ids.clear();
+        }
+        let live_indicator = if live_account.is_some() {
             LIVE_INDICATOR
         } else {
             OFFLINE_INDICATOR
         };
         let items_msg = ItemData::to_formatted_message(item_data, &run_info);
+        let locale = guild_cache_entry.settings.locale;
         let metadata = format!(
             "{} {} - {} {}",
             live_indicator,
             format_time(last_event.igt as u64),
-            split_desc,
-            self.ws_response.nickname.replace("_", SPECIAL_UNDERSCORE)
-        );
-        let message_content = format!(
-            "{}\n-# {}",
-            metadata.clone(),
-            roles_to_ping
-                .iter()
-                .map(|role_cache_entry| role_cache_entry.role.mention().to_string())
-                .collect::<Vec<_>>()
-                .join(" "),
+            locale.tr(split_desc.as_str()),
+            escape_markdown(self.get_display_name(guild_cache_entry).as_str())
         );
+        guild_cache_entry
+            .ping_state
+            .record_pings(&self.ws_response.nickname, &mentioned_role_ids);
+        if !digest_role_ids.is_empty() {
+            self.queue_digest(
+                guild_id,
+                guild_cache_entry,
+                metadata.clone(),
+                digest_role_ids,
+            );
+        }
+        let message_content = if mentions.is_empty() {
+            String::new()
+        } else {
+            format!("{}\n-# {}", metadata.clone(), mentions.join(" "))
+        };
         let pace_msg = format!(
             "{}  {} - {}",
             split_emoji,
             format_time(last_event.igt as u64),
-            split_desc,
+            locale.tr(split_desc.as_str()),
         );
+        let pace_embed = PaceEmbed {
+            author,
+            split: Some(run_info.split.clone()),
+            pace_msg,
+            items_msg,
+            live_account,
+            stats_link: self.stats_link.to_owned(),
+            last_updated: self.ws_response.last_updated,
+            is_bastionless: run_info.run_type == RunType::Bastionless,
+            pace_color: pace_quality_color(
+                last_event.igt as u64,
+                &guild_cache_entry.split_thresholds(&run_info.split, &self.ws_response.nickname),
+            ),
+            projection_msg: projection.map(|projection| projection.to_formatted_message()),
+            warning_msg,
+            locale,
+        };
+        if !subscribers_to_dm.is_empty() {
+            let dm_content = format!(
+                "{}\n-# {}",
+                metadata,
+                locale.trf("From {0}", &[guild_cache_entry.name.as_str()])
+            );
+            let dm_embed = pace_embed.render(&guild_cache_entry.settings.embed_template);
+            self.send_dm_notifications(
+                guild_id,
+                guild_cache_entry,
+                subscribers_to_dm,
+                dm_embed,
+                dm_content,
+            )
+            .await;
+        }
+        if !should_post {
+            return Ok(None);
+        }
         match self
             .send_message_in_pace_channel(
-                &guild_cache_entry.pace_channel,
-                guild_cache_entry.name.to_string(),
-                author,
-                pace_msg,
-                items_msg,
-                live_link,
-                Some(run_info),
-                message_content,
-                metadata,
-                roles_to_ping,
-                split_desc.to_string(),
-                true,
+                guild_cache_entry,
+                PaceMessage {
+                    embed: pace_embed,
+                    content: message_content,
+                    mentions,
+                    removable_mentions,
+                    split_desc: split_desc.to_string(),
+                    is_pace_event: true,
+                    reply_to,
+                },
             )
             .await
         {
-            Ok(_) => Ok(()),
+            Ok(message) => Ok(Some(message)),
             Err(err) => {
                 self.log.error(
                     format!("Failed to send split: '{}' due to: {}", split_desc, err).as_str(),
@@ -310,23 +645,23 @@ impl Dispatcher {
 
     pub async fn handle_non_pace_event(
         &self,
-        live_link: String,
-        author: CreateEmbedAuthor,
-        last_event: &Event,
+        guild_event: GuildEvent<'_>,
         guild_cache_entry: &mut GuildCacheEntry,
-        is_private: bool,
-        has_player_uuid: bool,
-    ) -> Result<(), Box<dyn Error>> {
-        let player_data = if has_player_uuid {
-            guild_cache_entry
-                .player_whitelist
-                .get_mut(&self.ws_response.user.uuid)
-                .unwrap()
-        } else {
-            guild_cache_entry
-                .player_whitelist
-                .get_mut(&self.ws_response.nickname.to_lowercase())
-                .unwrap()
+    ) -> Result<Option<Message>, Box<dyn Error>> {
+        let GuildEvent {
+            live_account,
+            author,
+            last_event,
+            is_private,
+            runner_key,
+            reply_to,
+            ..
+        } = guild_event;
+        let player_data = match guild_cache_entry.player_whitelist.get_mut(&runner_key) {
+            Some(player_data) => player_data,
+            None => {
+                return Err(format!("failed to get whitelist entry for: '{}'.", runner_key).into())
+            }
         };
 
         let runner_name = self.ws_response.nickname.to_owned();
@@ -339,7 +674,7 @@ impl Dispatcher {
                         "Skipping guild name: {} because it is not a sub 10 completion and the guild is public.", 
                         guild_cache_entry.name
                     ).as_str());
-                    return Ok(());
+                    return Ok(None);
                 }
                 // `minutes` + 1 will always be greater than minutes.
                 // This is done to send finish message always if finish time is not defined.
@@ -354,37 +689,51 @@ impl Dispatcher {
                 )
                 .as_str(),
             );
-            return Ok(());
+            return Ok(None);
         }
 
+        let warning_msg = self.cheat_warning(&guild_cache_entry.settings);
+        let locale = guild_cache_entry.settings.locale;
         let finish_msg = format!(
-            "{}  {} - Finish",
+            "{}  {} - {}",
             CREDITS_EMOJI,
             format_time(last_event.igt as u64),
+            locale.tr("Finish"),
         );
 
         let mut items_msg = String::new();
         ItemData::format_item_count(&mut items_msg, ROD_EMOJI, "0".to_string());
         ItemData::format_item_count(&mut items_msg, PEARL_EMOJI, "0".to_string());
 
-        match self
+        let message = match self
             .send_message_in_pace_channel(
-                &guild_cache_entry.pace_channel,
-                guild_cache_entry.name.to_string(),
-                author,
-                finish_msg,
-                items_msg,
-                live_link,
-                None,
-                String::new(),
-                String::new(),
-                Vec::new(),
-                "Finish".to_string(),
-                false,
+                guild_cache_entry,
+                PaceMessage {
+                    embed: PaceEmbed {
+                        author,
+                        split: None,
+                        pace_msg: finish_msg,
+                        items_msg,
+                        live_account,
+                        stats_link: self.stats_link.to_owned(),
+                        last_updated: self.ws_response.last_updated,
+                        is_bastionless: false,
+                        pace_color: None,
+                        projection_msg: None,
+                        warning_msg,
+                        locale,
+                    },
+                    content: String::new(),
+                    mentions: Vec::new(),
+                    removable_mentions: Vec::new(),
+                    split_desc: "Finish".to_string(),
+                    is_pace_event: false,
+                    reply_to,
+                },
             )
             .await
         {
-            Ok(_) => (),
+            Ok(message) => message,
             Err(err) => {
                 self.log
                     .error(format!("Failed to send split: 'Finish' due to: {}", err).as_str());
@@ -397,13 +746,13 @@ impl Dispatcher {
                 "Can't handle non pace event for guild name: {} because it is either a public server or does not have a leaderboard channel.", 
                 guild_cache_entry.name
             ).as_str());
-            return Ok(());
+            return Ok(Some(message));
         }
 
         match self
             .update_leaderboard(
                 guild_cache_entry.lb_channel.unwrap(),
-                runner_name.to_owned().replace("_", SPECIAL_UNDERSCORE),
+                escape_markdown(runner_name.as_str()),
                 (minutes, seconds),
             )
             .await
@@ -419,7 +768,7 @@ impl Dispatcher {
                 )
                     .as_str(),
                 );
-                Ok(())
+                Ok(Some(message))
             }
             Err(err) => Err(format!(
                 "failed to update leaderboard in guild name: {} for runner name: {} due to: {}",
@@ -431,6 +780,304 @@ impl Dispatcher {
         }
     }
 
+    pub async fn handle_run_end_event(
+        &self,
+        guild_cache_entry: &GuildCacheEntry,
+        run_messages: &[(ChannelId, MessageId)],
+    ) -> Result<(), Box<dyn Error>> {
+        let locale = guild_cache_entry.settings.locale;
+        // Pace messages can be deleted by ping stripping or by moderators, so
+        // missing ones are skipped instead of failing the whole run end.
+        let mut remaining_messages = Vec::new();
+        for (channel_id, message_id) in run_messages {
+            let message = match channel_id.message(&self.ctx.http, message_id).await {
+                Ok(message) => message,
+                Err(err) => {
+                    self.log.warn(
+                        format!(
+                            "Failed to get run message: {} in guild name: {}: {}",
+                            message_id, guild_cache_entry.name, err
+                        )
+                        .as_str(),
+                    );
+                    continue;
+                }
+            };
+            remaining_messages.push((channel_id.to_owned(), message_id.to_owned()));
+            let mut embed = match message.embeds.first() {
+                Some(embed) => CreateEmbed::from(embed.to_owned()),
+                None => continue,
+            };
+            embed.colour(RUN_ENDED_COLOR);
+            if let Err(err) = channel_id
+                .edit_message(&self.ctx.http, message_id, |m| m.set_embed(embed))
+                .await
+            {
+                self.log.warn(
+                    format!(
+                        "Failed to grey out run message: {} in guild name: {}: {}",
+                        message_id, guild_cache_entry.name, err
+                    )
+                    .as_str(),
+                );
+            }
+        }
+        let last_split = self
+            .last_split_desc(locale)
+            .unwrap_or(locale.tr("an unknown split"));
+        let content = locale.trf(
+            "{0}'s run ended at {1}.",
+            &[
+                escape_markdown(self.get_display_name(guild_cache_entry).as_str()).as_str(),
+                last_split.as_str(),
+            ],
+        );
+        // Replies to the last message that is still there, if there is one.
+        match remaining_messages.last() {
+            Some((channel_id, message_id)) => {
+                channel_id
+                    .send_message(&self.ctx.http, |m| {
+                        m.content(content)
+                            .reference_message((channel_id.to_owned(), message_id.to_owned()))
+                    })
+                    .await?
+            }
+            None => {
+                guild_cache_entry
+                    .pace_channel
+                    .send_message(&self.ctx.http, |m| m.content(content))
+                    .await?
+            }
+        };
+        self.log.info(
+            format!(
+                "Sent run end message for user with name: '{}' in guild name: {}.",
+                self.ws_response.nickname, guild_cache_entry.name,
+            )
+            .as_str(),
+        );
+        Ok(())
+    }
+
+    pub fn queue_digest(
+        &self,
+        guild_id: &GuildId,
+        guild_cache_entry: &mut GuildCacheEntry,
+        metadata: String,
+        role_ids: Vec<RoleId>,
+    ) {
+        let pending_digest = &mut guild_cache_entry.ping_state.pending_digest;
+        pending_digest.push((metadata, role_ids));
+        if pending_digest.len() > 1 {
+            return;
+        }
+        let delay = guild_cache_entry
+            .settings
+            .role_cooldown
+            .max(guild_cache_entry.settings.runner_cooldown);
+        let guild_id = guild_id.to_owned();
+        let pace_channel = guild_cache_entry.pace_channel;
+        let locale = guild_cache_entry.settings.locale;
+        let ctx_clone = self.ctx.clone();
+        let cache_clone = self.cache.clone();
+        let log_clone = self.log.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(delay)).await;
+            let pending_digest = {
+                let mut locked_cache = cache_clone.lock().await;
+                let guild_cache_entry = match locked_cache.entries.get_mut(&guild_id) {
+                    Some(guild_cache_entry) => guild_cache_entry,
+                    None => return,
+                };
+                let is_ping_allowed = guild_cache_entry
+                    .settings
+                    .is_ping_allowed(current_timestamp());
+                let ping_state = &mut guild_cache_entry.ping_state;
+                let pending_digest = std::mem::take(&mut ping_state.pending_digest);
+                // The pace messages were already posted, so a digest without
+                // mentions would only repeat them.
+                if !is_ping_allowed {
+                    log_clone.info(
+                        format!(
+                            "Dropping digest of {} pace-ping(s) because pings are muted by the schedule in guild with id: {}.",
+                            pending_digest.len(),
+                            guild_id
+                        )
+                        .as_str(),
+                    );
+                    return;
+                }
+                let now = Instant::now();
+                for (_, role_ids) in pending_digest.iter() {
+                    for role_id in role_ids {
+                        ping_state.role_pings.insert(role_id.to_owned(), now);
+                    }
+                }
+                pending_digest
+            };
+            let mut role_ids = pending_digest
+                .iter()
+                .flat_map(|(_, role_ids)| role_ids.to_owned())
+                .collect::<Vec<_>>();
+            role_ids.sort();
+            role_ids.dedup();
+            let content = format!(
+                "**{}**\n{}\n-# {}",
+                locale.tr("Pace during cooldown"),
+                pending_digest
+                    .iter()
+                    .map(|(metadata, _)| metadata.to_owned())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                role_ids
+                    .iter()
+                    .map(|role_id| role_id.mention().to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            );
+            match pace_channel
+                .send_message(&ctx_clone.http, |m| m.content(content))
+                .await
+            {
+                Ok(_) => log_clone.info(
+                    format!(
+                        "Sent digest of {} pace-ping(s) in guild with id: {}.",
+                        pending_digest.len(),
+                        guild_id
+                    )
+                    .as_str(),
+                ),
+                Err(err) => {
+                    log_clone.error(format!("failed to send digest due to: {}", err).as_str())
+                }
+            };
+        });
+    }
+
+    pub async fn send_dm_notifications(
+        &self,
+        guild_id: &GuildId,
+        guild_cache_entry: &mut GuildCacheEntry,
+        user_ids: Vec<UserId>,
+        embed: CreateEmbed,
+        content: String,
+    ) {
+        let mut disabled_user_ids = Vec::new();
+        for user_id in user_ids {
+            let result = match user_id.create_dm_channel(&self.ctx.http).await {
+                Ok(dm_channel) => dm_channel
+                    .send_message(&self.ctx.http, |m| {
+                        m.content(content.to_owned()).set_embed(embed.clone())
+                    })
+                    .await
+                    .map(|_| ()),
+                Err(err) => Err(err),
+            };
+            match result {
+                Ok(_) => {
+                    guild_cache_entry.ping_state.dm_failures.remove(&user_id);
+                }
+                Err(err) => {
+                    let failures = guild_cache_entry
+                        .ping_state
+                        .dm_failures
+                        .entry(user_id)
+                        .or_insert(0);
+                    *failures += 1;
+                    self.log.warn(
+                        format!(
+                            "Failed to send dm to user with id: {} in guild name: {} ({}/{}) due to: {}",
+                            user_id, guild_cache_entry.name, failures, DM_FAILURE_LIMIT, err
+                        )
+                        .as_str(),
+                    );
+                    if *failures >= DM_FAILURE_LIMIT {
+                        disabled_user_ids.push(user_id);
+                    }
+                }
+            };
+        }
+        if disabled_user_ids.is_empty() {
+            return;
+        }
+        for subscription in guild_cache_entry.subscriptions.iter_mut() {
+            if subscription.delivery == SubscriptionDelivery::Dm
+                && disabled_user_ids.contains(&subscription.user_id)
+            {
+                subscription.delivery = SubscriptionDelivery::Disabled;
+            }
+        }
+        for user_id in disabled_user_ids.iter() {
+            guild_cache_entry.ping_state.dm_failures.remove(user_id);
+        }
+        match self
+            .save_subscriptions(guild_id, &guild_cache_entry.subscriptions)
+            .await
+        {
+            Ok(_) => self.log.info(
+                format!(
+                    "Disabled dm subscriptions for {} user(s) in guild name: {}.",
+                    disabled_user_ids.len(),
+                    guild_cache_entry.name
+                )
+                .as_str(),
+            ),
+            Err(err) => self.log.error(
+                format!(
+                    "Failed to save disabled subscriptions in guild name: {} due to: {}",
+                    guild_cache_entry.name, err
+                )
+                .as_str(),
+            ),
+        };
+    }
+
+    pub async fn save_subscriptions(
+        &self,
+        guild_id: &GuildId,
+        subscriptions: &[SubscriptionCacheEntry],
+    ) -> Result<(), Box<dyn Error>> {
+        let (channel, message) =
+            get_config_message(&self.ctx, guild_id, PACEMANBOT_SUBSCRIPTIONS_CHANNEL).await?;
+        let contents = subscriptions
+            .iter()
+            .map(|subscription| subscription.to_config_line())
+            .collect::<Vec<_>>()
+            .join("\n");
+        set_config_message_contents(&self.ctx, &channel, message, contents).await
+    }
+
+    pub fn cheat_warning(&self, settings: &GuildSettings) -> Option<String> {
+        let cheat_flags = self.ws_response.cheat_flags();
+        if cheat_flags.is_empty() {
+            return None;
+        }
+        match settings.cheated_run_policy {
+            CheatedRunPolicy::NoPings | CheatedRunPolicy::Annotate => Some(cheat_flags.join(", ")),
+            CheatedRunPolicy::Suppress | CheatedRunPolicy::Allow => None,
+        }
+    }
+
+    pub fn last_split_desc(&self, locale: Locale) -> Option<String> {
+        let event_list = &self.ws_response.event_list;
+        let (idx, last_split_event) = event_list
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, evt)| matches!(EventType::from(*evt), EventType::PaceEvent))?;
+        let run_info = RunInfo::from_last_event(
+            last_split_event,
+            event_list[..=idx].to_vec(),
+            self.ws_response.context_event_list.to_vec(),
+        )?;
+        let split_desc = run_info.split.desc(&run_info.structure)?;
+        Some(format!(
+            "{} {}",
+            locale.tr(split_desc.as_str()),
+            format_time(last_split_event.igt as u64)
+        ))
+    }
+
     pub async fn update_leaderboard(
         &self,
         leaderboard_channel: ChannelId,
@@ -504,110 +1151,74 @@ impl Dispatcher {
 
     pub async fn send_message_in_pace_channel(
         &self,
-        pace_channel: &ChannelId,
-        guild_name: String,
-        author: CreateEmbedAuthor,
-        pace_msg: String,
-        items_msg: String,
-        live_link: String,
-        run_info: Option<RunInfo>,
-        message_content: String,
-        metadata: String,
-        roles_to_ping: Vec<&RoleCacheEntry>,
-        split_desc: String,
-        is_pace_event: bool,
-    ) -> Result<(), Box<dyn Error>> {
-        let run_info = run_info.unwrap_or(RunInfo::default());
-        match pace_channel
+        guild_cache_entry: &GuildCacheEntry,
+        pace_message: PaceMessage,
+    ) -> Result<Message, Box<dyn Error>> {
+        let PaceMessage {
+            embed: pace_embed,
+            content: message_content,
+            mentions,
+            removable_mentions,
+            split_desc,
+            is_pace_event,
+            reply_to,
+        } = pace_message;
+        let template = guild_cache_entry.settings.embed_template.clone();
+        let compact_msg = if template.compact {
+            Some(pace_embed.render_compact(&template))
+        } else {
+            None
+        };
+        let message = guild_cache_entry
+            .pace_channel
             .send_message(&self.ctx.clone(), |m| {
-                m.embed(|e| {
-                    e.set_author(author.clone());
-                    e.field(pace_msg.clone(), "", false);
-                    if !live_link.is_empty() {
-                        e.field(format!("{} {}", TWITCH_EMOJI, live_link.clone()), "", false);
-                    } else {
-                        e.field(format!("{}  Offline", OFFLINE_EMOJI), "", false);
-                    }
-                    e.field("Splits", format!("[Link]({})", self.stats_link), true);
-                    e.field(
-                        "Time",
-                        format!("<t:{}:R>", (self.ws_response.last_updated / 1000) as u64),
-                        true,
-                    );
-                    e.field("Items", items_msg.clone(), true);
-                    if is_pace_event && RunType::Bastionless == run_info.run_type {
-                        e.field("Bastionless", "Yes", true);
-                    }
-                    e
-                })
-                .content(message_content.to_owned())
+                match compact_msg.as_ref() {
+                    Some(compact_msg) if message_content.is_empty() => m.content(compact_msg),
+                    Some(compact_msg) => m.content(format!("{}\n{}", message_content, compact_msg)),
+                    None => m
+                        .set_embed(pace_embed.render(&template))
+                        .content(message_content.to_owned()),
+                };
+                if let Some(reply_to) = reply_to {
+                    m.reference_message(reply_to);
+                }
+                m
             })
-            .await
-        {
-            Ok(mut message) => {
-                if is_pace_event {
-                    let ctx_clone = self.ctx.clone();
-                    let ping_content_clone = message_content.to_owned();
-                    let metadata_clone = metadata.clone();
-                    let roles_to_ping_clone = roles_to_ping
-                        .into_iter()
-                        .map(|r| (r.runner.clone(), r.role.mention().to_string()))
-                        .collect::<Vec<_>>();
-                    let author_clone = author.clone();
-                    let pace_content_clone = pace_msg.clone();
-                    let live_link_clone = live_link.clone();
-                    let stats_link_clone = self.stats_link.clone();
-                    let item_data_content_clone = items_msg.clone();
-                    let live_account_clone = self.ws_response.user.live_account.is_some();
-                    let last_updated = self.ws_response.last_updated;
-                    let run_type_clone = run_info.run_type.clone();
-                    let log_clone = self.log.clone();
-
+            .await?;
+        if is_pace_event {
+            let settings = &guild_cache_entry.settings;
+            let mut message = message.clone();
+            let ctx_clone = self.ctx.clone();
+            let log_clone = self.log.clone();
+            match settings.ping_strip_mode {
+                PingStripMode::Keep => (),
+                PingStripMode::Strip => {
+                    let delay = settings.ping_strip_delay;
                     tokio::spawn(async move {
-                        sleep(Duration::from_secs(EDIT_MESSAGE_DELAY)).await;
-                        let removable_roles = roles_to_ping_clone
+                        sleep(Duration::from_secs(delay)).await;
+                        // Rebuilt from the mentions that stay, the metadata line is dropped.
+                        let kept_mentions = mentions
                             .iter()
-                            .filter(|(runner, _)| runner.as_str() != "")
-                            .map(|(_, mention)| mention)
+                            .filter(|mention| !removable_mentions.contains(mention))
+                            .map(|mention| mention.to_owned())
                             .collect::<Vec<_>>();
-                        let mut new_content = ping_content_clone;
-                        for role in removable_roles {
-                            let replaceable_str = format!("{} ", role);
-                            new_content = new_content.replace(replaceable_str.as_str(), "");
-                        }
-                        let content_removed_metadata =
-                            new_content.replace(format!("{}\n", metadata_clone).as_str(), "");
+                        let content_removed_metadata = if kept_mentions.is_empty() {
+                            String::new()
+                        } else {
+                            format!("-# {}", kept_mentions.join(" "))
+                        };
                         match message
-                            .edit(&ctx_clone.http, |m| {
-                                m.embed(|e| {
-                                    e.set_author(author_clone);
-                                    e.field(pace_content_clone, "", false);
-                                    if live_account_clone {
-                                        e.field(
-                                            format!("{} {}", TWITCH_EMOJI, live_link_clone),
-                                            "",
-                                            false,
-                                        );
-                                    } else {
-                                        e.field(format!("{}  Offline", OFFLINE_EMOJI), "", false);
-                                    }
-                                    e.field(
-                                        "Splits",
-                                        format!("[Link]({})", stats_link_clone),
-                                        true,
-                                    );
-                                    e.field(
-                                        "Time",
-                                        format!("<t:{}:R>", (last_updated / 1000) as u64),
-                                        true,
-                                    );
-                                    e.field("Items", item_data_content_clone, true);
-                                    if let RunType::Bastionless = run_type_clone {
-                                        e.field("Bastionless", "Yes", true);
-                                    }
-                                    e
-                                })
-                                .content(content_removed_metadata)
+                            .edit(&ctx_clone.http, |m| match compact_msg {
+                                Some(compact_msg) if content_removed_metadata.is_empty() => {
+                                    m.content(compact_msg)
+                                }
+                                Some(compact_msg) => m.content(format!(
+                                    "{}\n{}",
+                                    content_removed_metadata, compact_msg
+                                )),
+                                None => m
+                                    .set_embed(pace_embed.render(&template))
+                                    .content(content_removed_metadata),
                             })
                             .await
                         {
@@ -620,16 +1231,29 @@ impl Dispatcher {
                         };
                     });
                 }
-                self.log.info(
-                    format!(
-                    "Sent pace-ping for user with name: '{}' for split: '{}' in guild name: {}.",
-                    self.ws_response.nickname, split_desc, guild_name,
-                )
-                    .as_str(),
-                );
-                Ok(())
-            }
-            Err(err) => Err(err.into()),
+                PingStripMode::Delete => {
+                    let delay = settings.message_delete_delay * 60;
+                    tokio::spawn(async move {
+                        sleep(Duration::from_secs(delay)).await;
+                        match message.delete(&ctx_clone.http).await {
+                            Ok(_) => (),
+                            Err(err) => {
+                                log_clone.error(
+                                    format!("failed to delete message due to: {}", err).as_str(),
+                                );
+                            }
+                        };
+                    });
+                }
+            };
         }
+        self.log.info(
+            format!(
+                "Sent pace-ping for user with name: '{}' for split: '{}' in guild name: {}.",
+                self.ws_response.nickname, split_desc, guild_cache_entry.name,
+            )
+            .as_str(),
+        );
+        Ok(message)
     }
 }
@@ -3,6 +3,7 @@ use crate::ws::{Event, EventId};
 pub enum EventType {
     NonPaceEvent,
     PaceEvent,
+    RunEndEvent,
     Unknown,
 }
 
@@ -15,6 +16,7 @@ impl From<&Event> for EventType {
             | EventId::RsgEnterStronghold
             | EventId::RsgEnterEnd => EventType::PaceEvent,
             EventId::RsgCredits => EventType::NonPaceEvent,
+            EventId::CommonLeaveWorld => EventType::RunEndEvent,
             _ => EventType::Unknown,
         }
     }
@@ -0,0 +1,17 @@
+use serenity::{
+    builder::CreateEmbedAuthor,
+    model::id::{ChannelId, MessageId},
+};
+
+use crate::{dispatcher::Projection, ws::Event, ws::LiveAccount};
+
+// The last event of a run as seen by one guild.
+pub struct GuildEvent<'a> {
+    pub live_account: Option<LiveAccount>,
+    pub author: CreateEmbedAuthor,
+    pub last_event: &'a Event,
+    pub is_private: bool,
+    pub runner_key: String,
+    pub projection: Option<&'a Projection>,
+    pub reply_to: Option<(ChannelId, MessageId)>,
+}
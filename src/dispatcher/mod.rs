@@ -1,11 +1,19 @@
 mod consts;
 mod dispatcher;
 mod event_type;
+mod guild_event;
+mod pace_embed;
+mod pace_message;
+mod projection;
 mod run_info;
 mod utils;
 
 pub use consts::*;
 pub use dispatcher::Dispatcher;
 pub use event_type::EventType;
+pub use guild_event::GuildEvent;
+pub use pace_embed::PaceEmbed;
+pub use pace_message::PaceMessage;
+pub use projection::Projection;
 pub use run_info::{RunInfo, RunType};
 pub use utils::*;
@@ -0,0 +1,148 @@
+use serenity::builder::{CreateEmbed, CreateEmbedAuthor};
+
+use crate::{
+    cache::{EmbedTemplate, Split},
+    dispatcher::{OFFLINE_EMOJI, WARNING_INDICATOR},
+    locale::Locale,
+    ws::LiveAccount,
+};
+
+// Everything needed to build a pace embed, so that the sent, edited and
+// direct message copies are always rendered the same way.
+#[derive(Clone)]
+pub struct PaceEmbed {
+    pub author: CreateEmbedAuthor,
+    // `None` for finishes.
+    pub split: Option<Split>,
+    pub pace_msg: String,
+    pub items_msg: String,
+    pub live_account: Option<LiveAccount>,
+    pub stats_link: String,
+    pub last_updated: i64,
+    pub is_bastionless: bool,
+    // Colour by pace quality, see `pace_quality_color`.
+    pub pace_color: Option<u32>,
+    pub projection_msg: Option<String>,
+    pub warning_msg: Option<String>,
+    pub locale: Locale,
+}
+
+impl PaceEmbed {
+    pub fn render(&self, template: &EmbedTemplate) -> CreateEmbed {
+        let mut e = CreateEmbed::default();
+        e.set_author(self.author.clone());
+        e.field(template.replace_emojis(self.pace_msg.as_str()), "", false);
+        if template.has_field("live") {
+            e.field(template.replace_emojis(self.live_msg().as_str()), "", false);
+        }
+        if template.has_field("splits") {
+            e.field(
+                self.locale.tr("Splits"),
+                format!("[{}]({})", self.locale.tr("Link"), self.stats_link),
+                true,
+            );
+        }
+        if template.has_field("time") {
+            e.field(self.locale.tr("Time"), self.time_msg(), true);
+        }
+        if template.has_field("items") {
+            e.field(
+                self.locale.tr("Items"),
+                template.replace_emojis(self.items_msg.as_str()),
+                true,
+            );
+        }
+        if self.is_bastionless && template.has_field("bastionless") {
+            e.field(self.locale.tr("Bastionless"), self.locale.tr("Yes"), true);
+        }
+        if let Some(projection_msg) = self.projection_msg.as_ref() {
+            if template.has_field("projected") {
+                e.field(self.locale.tr("Projected"), projection_msg, true);
+            }
+        }
+        if let Some(warning_msg) = self.warning_msg.as_ref() {
+            if template.has_field("warning") {
+                e.field(
+                    format!("{} {}", WARNING_INDICATOR, self.locale.tr("Warning")),
+                    warning_msg,
+                    false,
+                );
+            }
+        }
+        let split_key = self.split_key();
+        if let Some(color) = template.split_colors.get(split_key.as_str()) {
+            e.colour(*color);
+        } else if let Some(color) = self.pace_color.filter(|_| template.pace_colors) {
+            e.colour(color);
+        }
+        if let Some(thumbnail) = template.split_thumbnails.get(split_key.as_str()) {
+            e.thumbnail(thumbnail);
+        }
+        e
+    }
+
+    pub fn render_compact(&self, template: &EmbedTemplate) -> String {
+        let mut parts = vec![self.pace_msg.to_owned()];
+        if template.has_field("live") {
+            parts.push(match self.live_account.as_ref() {
+                Some(live_account) => format!(
+                    "{} <{}>",
+                    live_account.platform.emoji(),
+                    live_account.link()
+                ),
+                None => self.live_msg(),
+            });
+        }
+        if template.has_field("splits") {
+            parts.push(format!(
+                "[{}](<{}>)",
+                self.locale.tr("Splits"),
+                self.stats_link
+            ));
+        }
+        if template.has_field("time") {
+            parts.push(self.time_msg());
+        }
+        if template.has_field("items") {
+            parts.push(self.items_msg.to_owned());
+        }
+        if self.is_bastionless && template.has_field("bastionless") {
+            parts.push(self.locale.tr("Bastionless"));
+        }
+        if let Some(projection_msg) = self.projection_msg.as_ref() {
+            if template.has_field("projected") {
+                parts.push(format!(
+                    "{} {}",
+                    self.locale.tr("Projected"),
+                    projection_msg
+                ));
+            }
+        }
+        if let Some(warning_msg) = self.warning_msg.as_ref() {
+            if template.has_field("warning") {
+                parts.push(format!("{} {}", WARNING_INDICATOR, warning_msg));
+            }
+        }
+        template.replace_emojis(parts.join(" | ").as_str())
+    }
+
+    fn live_msg(&self) -> String {
+        match self.live_account.as_ref() {
+            Some(live_account) => {
+                format!("{} {}", live_account.platform.emoji(), live_account.link())
+            }
+            None => format!("{}  {}", OFFLINE_EMOJI, self.locale.tr("Offline")),
+        }
+    }
+
+    fn time_msg(&self) -> String {
+        format!("<t:{}:R>", (self.last_updated / 1000) as u64)
+    }
+
+    fn split_key(&self) -> String {
+        match self.split.as_ref() {
+            Some(split) => split.to_str(),
+            None => "F".to_string(),
+        }
+    }
+}
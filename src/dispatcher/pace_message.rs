@@ -0,0 +1,15 @@
+use serenity::model::id::{ChannelId, MessageId};
+
+use crate::dispatcher::PaceEmbed;
+
+// A message for the pace channel, mentions in `removable_mentions` are
+// stripped again according to the guild's ping_strip_mode.
+pub struct PaceMessage {
+    pub embed: PaceEmbed,
+    pub content: String,
+    pub mentions: Vec<String>,
+    pub removable_mentions: Vec<String>,
+    pub split_desc: String,
+    pub is_pace_event: bool,
+    pub reply_to: Option<(ChannelId, MessageId)>,
+}
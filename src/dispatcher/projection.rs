@@ -0,0 +1,105 @@
+use crate::{
+    cache::{PaceHistoryEntry, Split, PACE_HISTORY_MIN_SAMPLES},
+    dispatcher::{
+        format_time, COMMUNITY_BLIND_FINISH_DELTA, COMMUNITY_END_ENTER_FINISH_DELTA,
+        COMMUNITY_EYE_SPY_FINISH_DELTA, COMMUNITY_FS_FINISH_DELTA, COMMUNITY_SS_FINISH_DELTA,
+    },
+};
+
+#[derive(Clone)]
+pub struct Projection {
+    pub split: Split,
+    pub finish: u64,
+    pub margin: u64,
+}
+
+impl Projection {
+    pub fn community_finish_delta(split: &Split) -> (u64, u64) {
+        match split {
+            Split::FirstStructure => COMMUNITY_FS_FINISH_DELTA,
+            Split::SecondStructure => COMMUNITY_SS_FINISH_DELTA,
+            Split::Blind => COMMUNITY_BLIND_FINISH_DELTA,
+            Split::EyeSpy => COMMUNITY_EYE_SPY_FINISH_DELTA,
+            Split::EndEnter => COMMUNITY_END_ENTER_FINISH_DELTA,
+        }
+    }
+
+    pub fn estimate(split: &Split, igt: u64, history: Option<&PaceHistoryEntry>) -> Self {
+        let deltas = history
+            .and_then(|history| history.get(split))
+            .filter(|deltas| deltas.len() >= PACE_HISTORY_MIN_SAMPLES);
+        let (delta, margin) = match deltas {
+            Some(deltas) => {
+                let count = deltas.len() as f64;
+                let mean = deltas.iter().sum::<u64>() as f64 / count;
+                let variance = deltas
+                    .iter()
+                    .map(|delta| (*delta as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / count;
+                (mean as u64, variance.sqrt() as u64)
+            }
+            None => Projection::community_finish_delta(split),
+        };
+        Self {
+            split: split.to_owned(),
+            finish: igt + delta,
+            margin,
+        }
+    }
+
+    // Maps the projected finish back onto the split's time scale so that split based role
+    // thresholds can be compared against the projection.
+    pub fn equivalent_split_time(&self) -> u64 {
+        let (delta, _) = Projection::community_finish_delta(&self.split);
+        self.finish.saturating_sub(delta)
+    }
+
+    pub fn to_formatted_message(&self) -> String {
+        format!(
+            "{} ± {}",
+            format_time(self.finish),
+            format_time(self.margin)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn community_estimate_without_enough_history() {
+        let projection = Projection::estimate(&Split::Blind, 300000, None);
+        assert_eq!((projection.finish, projection.margin), (540000, 60000));
+
+        let mut history = PaceHistoryEntry::default();
+        for _ in 1..PACE_HISTORY_MIN_SAMPLES {
+            history.record(Split::Blind, 100000);
+        }
+        let projection = Projection::estimate(&Split::Blind, 300000, Some(&history));
+        assert_eq!((projection.finish, projection.margin), (540000, 60000));
+    }
+
+    #[test]
+    fn estimate_from_history() {
+        let mut history = PaceHistoryEntry::default();
+        for delta in [100000, 200000, 300000] {
+            history.record(Split::EndEnter, delta);
+        }
+        let projection = Projection::estimate(&Split::EndEnter, 400000, Some(&history));
+        // Mean of 200000 and a standard deviation of sqrt(2/3) * 100000.
+        assert_eq!((projection.finish, projection.margin), (600000, 81649));
+    }
+
+    #[test]
+    fn equivalent_split_time_and_message() {
+        let projection = Projection {
+            split: Split::Blind,
+            finish: 540000,
+            margin: 60000,
+        };
+        assert_eq!(projection.equivalent_split_time(), 300000);
+        assert_eq!(projection.to_formatted_message(), "9:00 ± 1:00");
+    }
+}
@@ -104,4 +104,25 @@ impl RunInfo {
             }
         }
     }
+
+    pub fn split_times(event_list: &[Event]) -> Vec<(Split, u64)> {
+        let mut split_times = Vec::new();
+        let mut structures = event_list.iter().filter(|evt| {
+            evt.event_id == EventId::RsgEnterBastion || evt.event_id == EventId::RsgEnterFortress
+        });
+        if let Some(evt) = structures.next() {
+            split_times.push((Split::FirstStructure, evt.igt as u64));
+        }
+        if let Some(evt) = structures.next() {
+            split_times.push((Split::SecondStructure, evt.igt as u64));
+        }
+        for evt in event_list.iter() {
+            if let Some(split) = Split::from_event_id(&evt.event_id) {
+                if split_times.iter().all(|(s, _)| s != &split) {
+                    split_times.push((split, evt.igt as u64));
+                }
+            }
+        }
+        split_times
+    }
 }
@@ -1,3 +1,7 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::dispatcher::{PACE_BORDERLINE_COLOR, PACE_GREAT_COLOR, SPECIAL_UNDERSCORE};
+
 pub fn mins_secs_to_millis(time: (u8, u8)) -> u64 {
     let (minutes, seconds) = (time.0 as u64, time.1 as u64);
     minutes * 60000 + seconds * 1000
@@ -16,3 +20,101 @@ pub fn format_time(milliseconds: u64) -> String {
     let seconds = seconds_total % 60;
     format!("{}:{:02}", minutes, seconds)
 }
+
+// Underscores are swapped for a lookalike instead of escaped, because escaped
+// underscores still break discord's italics inside of embeds.
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '_' => escaped.push_str(SPECIAL_UNDERSCORE),
+            '*' | '~' | '`' | '|' | '>' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// Scales from yellow at the slowest threshold of a split to green at the fastest one.
+pub fn pace_quality_color(split_time: u64, thresholds: &[u64]) -> Option<u32> {
+    let slowest = thresholds.iter().max()?.to_owned();
+    let fastest = thresholds.iter().min()?.to_owned();
+    if split_time >= slowest {
+        return None;
+    }
+    // With a single threshold, pace 10% under it counts as great.
+    let fastest = if slowest == fastest {
+        slowest * 9 / 10
+    } else {
+        fastest
+    };
+    let quality = if split_time <= fastest {
+        1.0
+    } else {
+        (slowest - split_time) as f64 / (slowest - fastest) as f64
+    };
+    let blend = |borderline: u8, great: u8| {
+        (borderline as f64 + (great as f64 - borderline as f64) * quality).round() as u32
+    };
+    let (r, g, b) = (
+        blend(PACE_BORDERLINE_COLOR.0, PACE_GREAT_COLOR.0),
+        blend(PACE_BORDERLINE_COLOR.1, PACE_GREAT_COLOR.1),
+        blend(PACE_BORDERLINE_COLOR.2, PACE_GREAT_COLOR.2),
+    );
+    Some((r << 16) | (g << 8) | b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(color: (u8, u8, u8)) -> u32 {
+        ((color.0 as u32) << 16) | ((color.1 as u32) << 8) | color.2 as u32
+    }
+
+    #[test]
+    fn no_color_without_pace() {
+        assert_eq!(pace_quality_color(100, &[]), None);
+        assert_eq!(pace_quality_color(200, &[100, 200]), None);
+        assert_eq!(pace_quality_color(300, &[100, 200]), None);
+    }
+
+    #[test]
+    fn color_scales_between_thresholds() {
+        assert_eq!(
+            pace_quality_color(100, &[100, 200]),
+            Some(rgb(PACE_GREAT_COLOR))
+        );
+        assert_eq!(
+            pace_quality_color(50, &[200, 100]),
+            Some(rgb(PACE_GREAT_COLOR))
+        );
+        assert_eq!(
+            pace_quality_color(150, &[100, 200]),
+            Some(rgb((144, 200, 64)))
+        );
+        assert_eq!(
+            pace_quality_color(199999, &[100000, 200000]),
+            Some(rgb(PACE_BORDERLINE_COLOR))
+        );
+    }
+
+    #[test]
+    fn single_threshold_is_great_ten_percent_under() {
+        assert_eq!(
+            pace_quality_color(900, &[1000]),
+            Some(rgb(PACE_GREAT_COLOR))
+        );
+        assert_eq!(pace_quality_color(950, &[1000]), Some(rgb((144, 200, 64))));
+    }
+}
@@ -1,6 +1,7 @@
 use std::{env, error::Error};
 
 use dotenv::dotenv;
+use serenity::model::id::GuildId;
 
 pub struct Env {
     pub bot_token: String,
@@ -10,6 +11,14 @@ pub struct Env {
     pub webhook_url: String,
     pub webhook_name: String,
     pub log_level: String,
+    // Resolves uuids from this file instead of mojang when set.
+    pub uuid_resolver_file: String,
+    // Development servers that get the commands registered per guild. Commands
+    // are registered globally when this is empty.
+    pub dev_guild_ids: Vec<GuildId>,
+    // Removes the guild commands left from before commands were registered
+    // globally. Only needed once, as it makes a request per server on startup.
+    pub remove_guild_commands: bool,
 }
 
 impl Env {
@@ -32,6 +41,29 @@ impl Env {
         let webhook_url = env::var("WEBHOOK_URL").unwrap_or("".to_string());
         let webhook_name = env::var("WEBHOOK_NAME").unwrap_or("pacemanbotlogs".to_string());
         let log_level = env::var("LOG_LEVEL").unwrap_or("info".to_string());
+        let uuid_resolver_file = env::var("UUID_RESOLVER_FILE").unwrap_or("".to_string());
+        let mut dev_guild_ids = Vec::new();
+        for guild_id in env::var("DEV_GUILD_IDS")
+            .unwrap_or("".to_string())
+            .split(',')
+        {
+            let guild_id = guild_id.trim();
+            if guild_id.is_empty() {
+                continue;
+            }
+            match guild_id.parse::<u64>() {
+                Ok(guild_id) => dev_guild_ids.push(GuildId(guild_id)),
+                Err(e) => {
+                    return Err(format!(
+                        "Invalid guild id in DEV_GUILD_IDS: '{}': {}",
+                        guild_id, e
+                    )
+                    .into());
+                }
+            }
+        }
+        let remove_guild_commands = env::var("REMOVE_GUILD_COMMANDS")
+            .is_ok_and(|value| value.trim().eq_ignore_ascii_case("true"));
 
         Ok(Self {
             bot_token,
@@ -41,6 +73,9 @@ impl Env {
             webhook_url,
             webhook_name,
             log_level,
+            uuid_resolver_file,
+            dev_guild_ids,
+            remove_guild_commands,
         })
     }
 }
@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use serenity::{
     client::Context,
@@ -12,22 +12,34 @@ use serenity::{
 };
 
 use crate::{
-    cache::Cache,
-    command::get_default_commands,
+    cache::{Cache, ROLE_MESSAGE_REFRESH_DELAY},
+    command::{
+        load_config_history, refresh_role_messages, register_commands, remove_guild_commands,
+        CommandScope, SET_PINGS_CUSTOM_ID, WHITELIST_PAGE_CUSTOM_ID_PREFIX,
+    },
     config::{
         Config, PACEMANBOT_CHANNEL, PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL,
-        PACEMANBOT_RUNNER_NAMES_CHANNEL, ROLE_PREFIX, ROLE_PREFIX_115, ROLE_PREFIX_17,
+        PACEMANBOT_RUNNER_NAMES_CHANNEL, PACEMANBOT_SETTINGS_CHANNEL,
+        PACEMANBOT_SUBSCRIPTIONS_CHANNEL, ROLE_PREFIX, ROLE_PREFIX_115, ROLE_PREFIX_17,
         ROLE_PREFIX_AA,
     },
-    interaction::{handle_application_command_interaction, handle_message_component_interaction},
+    interaction::{
+        handle_application_command_interaction, handle_autocomplete_interaction,
+        handle_message_component_interaction, handle_modal_submit_interaction,
+    },
     log::Log,
+    resolver::UuidResolver,
     ws::WS,
 };
+use tokio::time::sleep;
 
 pub struct Handler {
     pub log: Arc<Log>,
     pub cache: Arc<Mutex<Cache>>,
     pub ws: Arc<WS>,
+    pub resolver: Arc<dyn UuidResolver>,
+    pub dev_guild_ids: Vec<GuildId>,
+    pub remove_guild_commands: bool,
 }
 
 impl Handler {
@@ -46,7 +58,14 @@ impl Handler {
                     );
                 }
             };
-            match handle_application_command_interaction(ctx, command).await {
+            match handle_application_command_interaction(
+                ctx,
+                command,
+                self.cache.clone(),
+                self.resolver.clone(),
+            )
+            .await
+            {
                 Ok(_) => (),
                 Err(err) => {
                     let content =
@@ -70,8 +89,30 @@ impl Handler {
                 return self.log.error(application_command_error.as_str());
             }
         }
+        if let Some(autocomplete) = interaction.as_autocomplete() {
+            // Autocomplete interactions can't be deferred and errors can't be shown to the user.
+            if let Err(err) =
+                handle_autocomplete_interaction(ctx, autocomplete, self.cache.clone()).await
+            {
+                self.log
+                    .error(format!("Failed to handle autocomplete interaction: {}", err).as_str());
+            }
+        }
         if let Some(message_component) = interaction.as_message_component() {
-            match message_component.defer_ephemeral(&ctx).await {
+            // Page buttons edit the message they are on instead of replying, and
+            // modals have to be the first response.
+            let defer_result = if message_component.data.custom_id == SET_PINGS_CUSTOM_ID {
+                Ok(())
+            } else if message_component
+                .data
+                .custom_id
+                .starts_with(WHITELIST_PAGE_CUSTOM_ID_PREFIX)
+            {
+                message_component.defer(&ctx).await
+            } else {
+                message_component.defer_ephemeral(&ctx).await
+            };
+            match defer_result {
                 Ok(_) => (),
                 Err(err) => {
                     return self.log.error(
@@ -83,7 +124,9 @@ impl Handler {
                     );
                 }
             };
-            match handle_message_component_interaction(ctx, message_component).await {
+            match handle_message_component_interaction(ctx, message_component, self.cache.clone())
+                .await
+            {
                 Ok(_) => (),
                 Err(err) => {
                     let content =
@@ -107,6 +150,38 @@ impl Handler {
                 return self.log.error(message_component_error.as_str());
             }
         }
+        if let Some(modal_submit) = interaction.as_modal_submit() {
+            match modal_submit.defer_ephemeral(&ctx).await {
+                Ok(_) => (),
+                Err(err) => {
+                    return self.log.error(
+                        format!("Failed to defer_ephemeral on modal submit: {}", err).as_str(),
+                    );
+                }
+            };
+            match handle_modal_submit_interaction(ctx, modal_submit, self.cache.clone()).await {
+                Ok(_) => (),
+                Err(err) => {
+                    let content = format!("Failed to handle modal submit interaction: {}", err);
+                    interaction_error = Some(content.to_string());
+                }
+            };
+            if let Some(modal_submit_error) = interaction_error.as_ref() {
+                match modal_submit
+                    .edit_original_interaction_response(&ctx.http, |m| {
+                        m.content(modal_submit_error.to_string())
+                    })
+                    .await
+                {
+                    Ok(_) => (),
+                    Err(err) => {
+                        self.log
+                            .error(format!("Failed to edit modal submit: {}", err).as_str());
+                    }
+                };
+                return self.log.error(modal_submit_error.as_str());
+            }
+        }
     }
 
     pub async fn handle_guild_role_events(&self, ctx: &Context, new: Role, guild_id: GuildId) {
@@ -123,6 +198,40 @@ impl Handler {
             );
         }
         self.update_cache(ctx, guild_id).await;
+        self.schedule_role_message_refresh(ctx, guild_id).await;
+    }
+
+    async fn schedule_role_message_refresh(&self, ctx: &Context, guild_id: GuildId) {
+        if !self
+            .cache
+            .lock()
+            .await
+            .pending_role_message_refreshes
+            .insert(guild_id)
+        {
+            return;
+        }
+        let ctx = ctx.clone();
+        let cache = self.cache.clone();
+        let log = self.log.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_secs(ROLE_MESSAGE_REFRESH_DELAY)).await;
+            cache
+                .lock()
+                .await
+                .pending_role_message_refreshes
+                .remove(&guild_id);
+            match refresh_role_messages(&ctx, &guild_id).await {
+                Ok(_) => (),
+                Err(err) => log.error(
+                    format!(
+                        "Failed to refresh role messages for guild: {}: {}",
+                        guild_id, err
+                    )
+                    .as_str(),
+                ),
+            }
+        });
     }
 
     pub async fn handle_channel_events(
@@ -134,7 +243,9 @@ impl Handler {
         match channel.name.as_str() {
             PACEMANBOT_RUNNER_NAMES_CHANNEL
             | PACEMANBOT_CHANNEL
-            | PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL => {
+            | PACEMANBOT_RUNNER_LEADERBOARD_CHANNEL
+            | PACEMANBOT_SETTINGS_CHANNEL
+            | PACEMANBOT_SUBSCRIPTIONS_CHANNEL => {
                 self.update_cache(ctx, guild_id).await;
             }
             _ => {
@@ -166,11 +277,16 @@ impl Handler {
                 );
             }
         };
-        if name != PACEMANBOT_RUNNER_NAMES_CHANNEL {
+        if name != PACEMANBOT_RUNNER_NAMES_CHANNEL
+            && name != PACEMANBOT_SETTINGS_CHANNEL
+            && name != PACEMANBOT_SUBSCRIPTIONS_CHANNEL
+        {
             return self.log.info(
                 format!(
-                    "Skipping message delete because it was not sent in #{}.",
+                    "Skipping message delete because it was not sent in #{}, #{} or #{}.",
                     PACEMANBOT_RUNNER_NAMES_CHANNEL,
+                    PACEMANBOT_SETTINGS_CHANNEL,
+                    PACEMANBOT_SUBSCRIPTIONS_CHANNEL,
                 )
                 .as_str(),
             );
@@ -191,31 +307,82 @@ impl Handler {
     }
 
     pub async fn handle_guild_create(&self, ctx: &Context, guild_id: GuildId) {
-        match GuildId::set_application_commands(&guild_id, &ctx.http, |commands| {
-            let default_commands = get_default_commands();
-            for command in default_commands {
-                commands.create_application_command(move |c| {
-                    c.name(command.name()).description(command.description());
-                    command.create_options(c)
-                });
+        if self.dev_guild_ids.contains(&guild_id) {
+            match register_commands(ctx, CommandScope::Guild(guild_id)).await {
+                Ok(summary) => self.log.info(
+                    format!(
+                        "Registered commands for guild {}: {}",
+                        guild_id,
+                        summary.to_str()
+                    )
+                    .as_str(),
+                ),
+                Err(err) => self
+                    .log
+                    .error(format!("Error creating command: {}", err).as_str()),
+            }
+        } else if self.dev_guild_ids.is_empty() && self.remove_guild_commands {
+            match remove_guild_commands(ctx, guild_id).await {
+                Ok(0) => (),
+                Ok(removed) => self.log.info(
+                    format!(
+                        "Removed {} guild commands in favour of global ones for guild: {}",
+                        removed, guild_id
+                    )
+                    .as_str(),
+                ),
+                Err(err) => self
+                    .log
+                    .error(format!("Error removing guild commands: {}", err).as_str()),
             }
-            commands
-        })
-        .await
-        {
-            Ok(_) => (),
-            Err(err) => self
-                .log
-                .error(format!("Error creating command: {}", err).as_str()),
         }
         ctx.set_presence(Some(Activity::watching("paceman.gg")), OnlineStatus::Online)
             .await;
         self.update_cache(ctx, guild_id).await;
+        // Guilds become available again after reconnecting, the history is only read once.
+        if !self
+            .cache
+            .lock()
+            .await
+            .config_history
+            .contains_key(&guild_id)
+        {
+            let history = load_config_history(ctx, &guild_id)
+                .await
+                .map_err(|err| err.to_string());
+            match history {
+                Ok(history) => {
+                    self.cache
+                        .lock()
+                        .await
+                        .config_history
+                        .entry(guild_id)
+                        .or_insert(history);
+                }
+                Err(err) => self.log.error(
+                    format!(
+                        "Error loading config history for guild {}: {}",
+                        guild_id, err
+                    )
+                    .as_str(),
+                ),
+            };
+        }
     }
 
     pub async fn handle_ready(&self, ctx: Context, ready: Ready) {
         self.log
             .info(format!("{} is connected!", ready.user.name).as_str());
+        if self.dev_guild_ids.is_empty() {
+            match register_commands(&ctx, CommandScope::Global).await {
+                Ok(summary) => self
+                    .log
+                    .info(format!("Registered global commands: {}", summary.to_str()).as_str()),
+                Err(err) => self
+                    .log
+                    .error(format!("Error creating global commands: {}", err).as_str()),
+            }
+        }
         let ws = self.ws.clone();
         let log = self.log.clone();
         let cache = self.cache.clone();
@@ -228,7 +395,12 @@ impl Handler {
     pub async fn update_cache(&self, ctx: &Context, guild_id: GuildId) {
         let mut locked_cache = self.cache.lock().await;
         match Config::parse_config_for_guild(ctx, guild_id).await {
-            Ok(guild_cache_entry) => locked_cache.entries.insert(guild_id, guild_cache_entry),
+            Ok(mut guild_cache_entry) => {
+                if let Some(old_guild_cache_entry) = locked_cache.entries.remove(&guild_id) {
+                    guild_cache_entry.ping_state = old_guild_cache_entry.ping_state;
+                }
+                locked_cache.entries.insert(guild_id, guild_cache_entry)
+            }
             Err(err) => {
                 return self
                     .log
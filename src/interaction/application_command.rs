@@ -1,14 +1,24 @@
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
 use serenity::{
-    client::Context, model::prelude::application_command::ApplicationCommandInteraction,
+    client::Context, futures::lock::Mutex,
+    model::prelude::application_command::ApplicationCommandInteraction,
 };
 
-use crate::command::{get_default_commands, CommandContext};
+use crate::{
+    cache::Cache,
+    command::{
+        format_options, get_config_snapshot, get_default_commands, record_config_change,
+        CommandContext, CommandOptions,
+    },
+    resolver::UuidResolver,
+};
 
 pub async fn handle_application_command_interaction(
     ctx: &Context,
     command: &ApplicationCommandInteraction,
+    cache: Arc<Mutex<Cache>>,
+    resolver: Arc<dyn UuidResolver>,
 ) -> Result<(), Box<dyn Error>> {
     let guild_id = match command.guild_id {
         Some(guild_id) => guild_id,
@@ -36,18 +46,59 @@ pub async fn handle_application_command_interaction(
         }
     };
 
-    match default_command
+    let before = if default_command.changes_config(CommandOptions::new(&command.data.options)) {
+        get_config_snapshot(ctx, &guild_id).await.ok()
+    } else {
+        None
+    };
+
+    let result = default_command
         .execute(CommandContext {
             ctx,
             guild_id,
             interaction: command,
+            cache: cache.clone(),
+            resolver,
         })
         .await
-    {
+        .map_err(|err| err.to_string());
+
+    let audit_result = match before {
+        Some(before) => {
+            let action = format!(
+                "{} {}",
+                command.data.name,
+                format_options(&command.data.options)
+            );
+            record_config_change(
+                ctx,
+                &guild_id,
+                cache,
+                &command.user,
+                action.trim(),
+                before,
+                result.as_ref().err().map(|err| err.as_str()),
+            )
+            .await
+            .map_err(|err| err.to_string())
+        }
+        None => Ok(()),
+    };
+    match result {
         Ok(_) => (),
         Err(err) => {
             return Err(format!("failed to execute command: {}", err).into());
         }
     };
+    match audit_result {
+        Ok(_) => (),
+        Err(err) => {
+            return Err(format!(
+                "the command succeeded, but failed to record the change: {}",
+                err
+            )
+            .into());
+        }
+    };
     Ok(())
 }
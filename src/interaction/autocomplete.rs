@@ -0,0 +1,51 @@
+use std::{error::Error, sync::Arc};
+
+use serenity::{
+    client::Context, futures::lock::Mutex, model::prelude::autocomplete::AutocompleteInteraction,
+};
+
+use crate::{
+    cache::Cache,
+    command::{get_default_commands, AutocompleteContext},
+};
+
+pub async fn handle_autocomplete_interaction(
+    ctx: &Context,
+    autocomplete: &AutocompleteInteraction,
+    cache: Arc<Mutex<Cache>>,
+) -> Result<(), Box<dyn Error>> {
+    let guild_id = match autocomplete.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return Err(format!(
+                "failed to get guild id for autocomplete: '{}'.",
+                autocomplete.data.name
+            )
+            .into())
+        }
+    };
+    let default_command = match get_default_commands()
+        .into_iter()
+        .find(|default_command| default_command.name() == autocomplete.data.name.as_str())
+    {
+        Some(default_command) => default_command,
+        None => {
+            return Err(format!("failed to handle autocomplete: {}", autocomplete.data.name).into())
+        }
+    };
+    match default_command
+        .autocomplete(AutocompleteContext {
+            ctx,
+            guild_id,
+            interaction: autocomplete,
+            cache,
+        })
+        .await
+    {
+        Ok(_) => (),
+        Err(err) => {
+            return Err(format!("failed to autocomplete command: {}", err).into());
+        }
+    };
+    Ok(())
+}
@@ -1,32 +1,60 @@
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
-use serenity::{client::Context, model::prelude::message_component::MessageComponentInteraction};
+use serenity::{
+    client::Context, futures::lock::Mutex,
+    model::prelude::message_component::MessageComponentInteraction,
+};
 
 use crate::{
-    cache::Split,
-    interaction::{handle_remove_pmb_roles, handle_select_role},
+    cache::{Cache, Split},
+    command::{SET_PINGS_CUSTOM_ID, WHITELIST_PAGE_CUSTOM_ID_PREFIX},
+    interaction::{
+        handle_my_pings, handle_open_set_pings, handle_remove_pmb_roles, handle_select_role,
+        handle_whitelist_import, handle_whitelist_page,
+    },
 };
 
 pub async fn handle_message_component_interaction(
     ctx: &Context,
     message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
 ) -> Result<(), Box<dyn Error>> {
-    let custom_id = match message_component.data.custom_id.as_str() {
-        "remove_pmb_roles" => handle_remove_pmb_roles(&ctx, &message_component).await,
+    // Splits with more pace-roles than fit in one menu get `<custom id>:<index>` menus.
+    let custom_id = match message_component.data.custom_id.split_once(':') {
+        Some((custom_id, _)) if custom_id.starts_with("select_") => custom_id,
+        _ => message_component.data.custom_id.as_str(),
+    };
+    let result = match custom_id {
+        "remove_pmb_roles" => handle_remove_pmb_roles(&ctx, &message_component, cache).await,
+        "my_pings" => handle_my_pings(ctx, message_component, cache).await,
+        SET_PINGS_CUSTOM_ID => handle_open_set_pings(ctx, message_component, cache).await,
         "select_structure1_role" => {
-            handle_select_role(&ctx, &message_component, Split::FirstStructure).await
+            handle_select_role(&ctx, &message_component, cache, Split::FirstStructure).await
         }
         "select_structure2_role" => {
-            handle_select_role(&ctx, &message_component, Split::SecondStructure).await
+            handle_select_role(&ctx, &message_component, cache, Split::SecondStructure).await
+        }
+        "select_blind_role" => {
+            handle_select_role(&ctx, &message_component, cache, Split::Blind).await
+        }
+        "select_eye_spy_role" => {
+            handle_select_role(&ctx, &message_component, cache, Split::EyeSpy).await
         }
-        "select_blind_role" => handle_select_role(&ctx, &message_component, Split::Blind).await,
-        "select_eye_spy_role" => handle_select_role(&ctx, &message_component, Split::EyeSpy).await,
         "select_end_enter_role" => {
-            handle_select_role(&ctx, &message_component, Split::EndEnter).await
+            handle_select_role(&ctx, &message_component, cache, Split::EndEnter).await
+        }
+        "whitelist_import_confirm" => {
+            handle_whitelist_import(ctx, message_component, cache, true).await
+        }
+        "whitelist_import_cancel" => {
+            handle_whitelist_import(ctx, message_component, cache, false).await
+        }
+        custom_id if custom_id.starts_with(WHITELIST_PAGE_CUSTOM_ID_PREFIX) => {
+            handle_whitelist_page(ctx, message_component, cache).await
         }
         _ => Err(format!("unknown custom id: {}.", message_component.data.custom_id).into()),
     };
-    match custom_id {
+    match result {
         Ok(_) => (),
         Err(err) => {
             return Err(format!("error while handling interaction: {}", err).into());
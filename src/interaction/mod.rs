@@ -1,8 +1,11 @@
 mod application_command;
+mod autocomplete;
 mod message_component;
+mod modal_submit;
 mod utils;
 
 pub use application_command::handle_application_command_interaction;
+pub use autocomplete::handle_autocomplete_interaction;
 pub use message_component::handle_message_component_interaction;
+pub use modal_submit::handle_modal_submit_interaction;
 pub use utils::*;
-
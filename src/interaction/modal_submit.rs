@@ -0,0 +1,25 @@
+use std::{error::Error, sync::Arc};
+
+use serenity::{
+    client::Context, futures::lock::Mutex, model::prelude::modal::ModalSubmitInteraction,
+};
+
+use crate::{cache::Cache, command::SET_PINGS_MODAL_CUSTOM_ID, interaction::handle_set_pings};
+
+pub async fn handle_modal_submit_interaction(
+    ctx: &Context,
+    modal_submit: &ModalSubmitInteraction,
+    cache: Arc<Mutex<Cache>>,
+) -> Result<(), Box<dyn Error>> {
+    let result = match modal_submit.data.custom_id.as_str() {
+        SET_PINGS_MODAL_CUSTOM_ID => handle_set_pings(ctx, modal_submit, cache).await,
+        _ => Err(format!("unknown custom id: {}.", modal_submit.data.custom_id).into()),
+    };
+    match result {
+        Ok(_) => (),
+        Err(err) => {
+            return Err(format!("error while handling modal submit: {}", err).into());
+        }
+    };
+    Ok(())
+}
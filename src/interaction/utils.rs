@@ -1,22 +1,40 @@
-use std::error::Error;
+use std::{error::Error, sync::Arc};
 
 use serenity::{
+    builder::CreateActionRow,
     client::Context,
+    futures::lock::Mutex,
     model::{
-        guild::Member,
+        guild::{Member, Role},
         id::{GuildId, RoleId},
-        prelude::message_component::MessageComponentInteraction,
+        prelude::{
+            component::{ActionRowComponent, InputTextStyle},
+            message_component::MessageComponentInteraction,
+            modal::ModalSubmitInteraction,
+            InteractionResponseType,
+        },
     },
 };
 
 use crate::{
-    cache::Split,
-    config::{ROLE_PREFIX, ROLE_PREFIX_115, ROLE_PREFIX_17, ROLE_PREFIX_AA},
+    cache::{Cache, Split, SPLITS},
+    command::{
+        create_whitelist_page, get_config_message, get_config_snapshot, get_guild_locale,
+        get_new_config_contents, get_role_selection_roles, parse_whitelist, record_config_change,
+        set_config_message_contents, MAX_INPUT_PLACEHOLDER_LENGTH, SET_PINGS_MODAL_CUSTOM_ID,
+        WHITELIST_PAGE_CUSTOM_ID_PREFIX,
+    },
+    config::{
+        extract_split_from_pb_role_name, extract_split_from_role_name, parse_time_threshold,
+        PACEMANBOT_RUNNER_NAMES_CHANNEL, ROLE_PREFIX, ROLE_PREFIX_115, ROLE_PREFIX_17,
+        ROLE_PREFIX_AA,
+    },
 };
 
 pub async fn handle_remove_pmb_roles(
     ctx: &Context,
     message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
 ) -> Result<(), Box<dyn Error>> {
     let guild_id = match message_component.guild_id {
         Some(guild_id) => guild_id,
@@ -42,8 +60,11 @@ pub async fn handle_remove_pmb_roles(
 
     remove_roles_starting_with(&ctx, &guild_id, &mut member, ROLE_PREFIX, false).await?;
 
+    let locale = get_guild_locale(&cache, &guild_id).await;
     message_component
-        .edit_original_interaction_response(&ctx.http, |r| r.content("PaceManBot roles removed"))
+        .edit_original_interaction_response(&ctx.http, |r| {
+            r.content(locale.tr("PaceManBot roles removed"))
+        })
         .await?;
     Ok(())
 }
@@ -75,6 +96,7 @@ pub async fn remove_roles_starting_with(
 pub async fn handle_select_role(
     ctx: &Context,
     message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
     split: Split,
 ) -> Result<(), Box<dyn Error>> {
     let split_str = split.to_str();
@@ -153,9 +175,392 @@ pub async fn handle_select_role(
 
     member.add_roles(&ctx, &roles_to_add).await?;
 
+    let locale = get_guild_locale(&cache, &guild_id).await;
+    message_component
+        .edit_original_interaction_response(&ctx.http, |r| r.content(locale.tr("Roles updated")))
+        .await?;
+
+    Ok(())
+}
+
+pub async fn handle_whitelist_import(
+    ctx: &Context,
+    message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
+    confirm: bool,
+) -> Result<(), Box<dyn Error>> {
+    let guild_id = match message_component.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return Err(format!(
+                "failed to get guild id for message component: {:#?}.",
+                message_component,
+            )
+            .into())
+        }
+    };
+    let pending_import = cache
+        .lock()
+        .await
+        .pending_imports
+        .remove(&(guild_id, message_component.user.id));
+    let pending_import = match pending_import {
+        Some(pending_import) if !pending_import.is_expired() => pending_import,
+        _ => {
+            return Err("no pending whitelist import, run /whitelist_import again."
+                .to_string()
+                .into())
+        }
+    };
+    let locale = get_guild_locale(&cache, &guild_id).await;
+    if !confirm {
+        message_component
+            .edit_original_interaction_response(&ctx.http, |r| {
+                r.content(locale.tr("Import cancelled."))
+            })
+            .await?;
+        return Ok(());
+    }
+
+    let (channel, message) =
+        get_config_message(ctx, &guild_id, PACEMANBOT_RUNNER_NAMES_CHANNEL).await?;
+    if get_new_config_contents(parse_whitelist(&message)?) != pending_import.base_contents {
+        return Err(format!(
+            "#{} changed since the preview, run /whitelist_import again.",
+            PACEMANBOT_RUNNER_NAMES_CHANNEL
+        )
+        .into());
+    }
+    let runner_count = pending_import.players.len();
+    let before = get_config_snapshot(ctx, &guild_id).await.ok();
+    set_config_message_contents(
+        ctx,
+        &channel,
+        message,
+        get_new_config_contents(pending_import.players),
+    )
+    .await?;
+    let audit_result = match before {
+        Some(before) => record_config_change(
+            ctx,
+            &guild_id,
+            cache,
+            &message_component.user,
+            "whitelist_import (confirmed)",
+            before,
+            None,
+        )
+        .await
+        .map_err(|err| err.to_string()),
+        None => Ok(()),
+    };
+
+    message_component
+        .edit_original_interaction_response(&ctx.http, |r| {
+            r.content(locale.trf(
+                "Imported whitelist with {0} runners.",
+                &[runner_count.to_string().as_str()],
+            ))
+        })
+        .await?;
+    match audit_result {
+        Ok(_) => (),
+        Err(err) => {
+            return Err(format!(
+                "the import succeeded, but failed to record the change: {}",
+                err
+            )
+            .into());
+        }
+    };
+    Ok(())
+}
+
+pub async fn handle_whitelist_page(
+    ctx: &Context,
+    message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
+) -> Result<(), Box<dyn Error>> {
+    let guild_id = match message_component.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return Err(format!(
+                "failed to get guild id for message component: {:#?}.",
+                message_component,
+            )
+            .into())
+        }
+    };
+    let custom_id = message_component
+        .data
+        .custom_id
+        .trim_start_matches(WHITELIST_PAGE_CUSTOM_ID_PREFIX);
+    let (page, search) = match custom_id.split_once(':') {
+        Some((page, search)) => (page.parse::<usize>()?, search),
+        None => return Err(format!("failed to parse page from custom id: {}.", custom_id).into()),
+    };
+    let players = match cache.lock().await.entries.get(&guild_id) {
+        Some(guild_cache_entry) => guild_cache_entry.player_whitelist.to_owned(),
+        None => {
+            return Err(format!("failed to get cache entry for guild id: {}.", guild_id).into())
+        }
+    };
+    let locale = get_guild_locale(&cache, &guild_id).await;
+    let (embed, action_row) = create_whitelist_page(&players, page, search, locale);
+    message_component
+        .edit_original_interaction_response(&ctx.http, |r| {
+            r.set_embed(embed)
+                .components(|c| c.add_action_row(action_row))
+        })
+        .await?;
+    Ok(())
+}
+
+fn get_pace_role_split(role: &Role) -> Option<Split> {
+    if role.name.contains("PB") {
+        extract_split_from_pb_role_name(&role.name)
+    } else {
+        extract_split_from_role_name(&role.name)
+            .ok()
+            .map(|(split, _, _)| split)
+    }
+}
+
+// `PB` or the `m:ss` time of a pace-role, as entered in the set pings modal.
+fn get_pace_role_threshold(role: &Role) -> Option<String> {
+    if role.name.contains("PB") {
+        return Some("PB".to_string());
+    }
+    extract_split_from_role_name(&role.name)
+        .ok()
+        .map(|(_, minutes, seconds)| format!("{}:{:02}", minutes, seconds))
+}
+
+fn get_split_roles<'a>(roles: &'a [Role], split: &Split) -> Vec<&'a Role> {
+    roles
+        .iter()
+        .filter(|role| get_pace_role_split(role).as_ref() == Some(split))
+        .collect()
+}
+
+fn join_thresholds(roles: &[&Role]) -> String {
+    roles
+        .iter()
+        .filter_map(|role| get_pace_role_threshold(role))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+pub async fn handle_my_pings(
+    ctx: &Context,
+    message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
+) -> Result<(), Box<dyn Error>> {
+    let guild_id = match message_component.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return Err(format!(
+                "failed to get guild id for message component: {:#?}.",
+                message_component,
+            )
+            .into())
+        }
+    };
+    let member = guild_id.member(&ctx, message_component.user.id).await?;
+    let (roles, _) = get_role_selection_roles(ctx, &guild_id).await?;
+    let locale = get_guild_locale(&cache, &guild_id).await;
+
+    let mut lines = Vec::new();
+    for split in SPLITS {
+        let member_roles = get_split_roles(&roles, &split)
+            .into_iter()
+            .filter(|role| member.roles.contains(&role.id))
+            .collect::<Vec<_>>();
+        if member_roles.is_empty() {
+            continue;
+        }
+        lines.push(format!(
+            "**{}**: {}",
+            locale.tr(split.alt_desc().as_str()),
+            join_thresholds(&member_roles)
+        ));
+    }
+    let content = if lines.is_empty() {
+        locale.tr("You don't have any pace-roles yet.")
+    } else {
+        format!("{}\n{}", locale.tr("Your pace-roles:"), lines.join("\n"))
+    };
+    message_component
+        .edit_original_interaction_response(&ctx.http, |r| r.content(content))
+        .await?;
+    Ok(())
+}
+
+// Opens a modal with one field per split, filled in with the member's current pace-roles.
+pub async fn handle_open_set_pings(
+    ctx: &Context,
+    message_component: &MessageComponentInteraction,
+    cache: Arc<Mutex<Cache>>,
+) -> Result<(), Box<dyn Error>> {
+    let guild_id = match message_component.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return Err(format!(
+                "failed to get guild id for message component: {:#?}.",
+                message_component,
+            )
+            .into())
+        }
+    };
+    let member = guild_id.member(&ctx, message_component.user.id).await?;
+    let (roles, _) = get_role_selection_roles(ctx, &guild_id).await?;
+    let locale = get_guild_locale(&cache, &guild_id).await;
+
+    let mut rows = Vec::new();
+    for split in SPLITS {
+        let split_roles = get_split_roles(&roles, &split);
+        if split_roles.is_empty() {
+            continue;
+        }
+        let member_roles = split_roles
+            .iter()
+            .filter(|role| member.roles.contains(&role.id))
+            .copied()
+            .collect::<Vec<_>>();
+        let placeholder = locale
+            .trf(
+                "Times like 3:30 or PB, comma separated. Any of: {0}",
+                &[join_thresholds(&split_roles).as_str()],
+            )
+            .chars()
+            .take(MAX_INPUT_PLACEHOLDER_LENGTH)
+            .collect::<String>();
+        let mut row = CreateActionRow::default();
+        row.create_input_text(|i| {
+            i.custom_id(split.to_str())
+                .label(locale.tr(split.alt_desc().as_str()))
+                .style(InputTextStyle::Short)
+                .placeholder(placeholder)
+                .required(false);
+            if !member_roles.is_empty() {
+                i.value(join_thresholds(&member_roles));
+            }
+            i
+        });
+        rows.push(row);
+    }
+
+    // Modals need at least one field.
+    if rows.is_empty() {
+        message_component
+            .create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.ephemeral(true)
+                            .content(locale.tr("No pace-roles are set up in this server."))
+                    })
+            })
+            .await?;
+        return Ok(());
+    }
     message_component
-        .edit_original_interaction_response(&ctx.http, |r| r.content("Roles updated"))
+        .create_interaction_response(&ctx.http, |r| {
+            r.kind(InteractionResponseType::Modal)
+                .interaction_response_data(|d| {
+                    d.custom_id(SET_PINGS_MODAL_CUSTOM_ID)
+                        .title(locale.tr("Set your pings"))
+                        .components(|c| c.set_action_rows(rows))
+                })
+        })
         .await?;
+    Ok(())
+}
+
+// Sets the pace-roles of every split in the modal at once. All fields are
+// validated before any role is changed.
+pub async fn handle_set_pings(
+    ctx: &Context,
+    modal_submit: &ModalSubmitInteraction,
+    cache: Arc<Mutex<Cache>>,
+) -> Result<(), Box<dyn Error>> {
+    let guild_id = match modal_submit.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return Err(format!(
+                "failed to get guild id for modal submit: {:#?}.",
+                modal_submit,
+            )
+            .into())
+        }
+    };
+    let mut member = guild_id.member(&ctx, modal_submit.user.id).await?;
+    let (roles, _) = get_role_selection_roles(ctx, &guild_id).await?;
+
+    let mut roles_to_add = Vec::new();
+    let mut roles_to_remove = Vec::new();
+    for component in modal_submit
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+    {
+        let input = match component {
+            ActionRowComponent::InputText(input) => input,
+            _ => continue,
+        };
+        let split = match Split::from_str(input.custom_id.as_str()) {
+            Some(split) => split,
+            None => return Err(format!("unknown split: '{}'.", input.custom_id).into()),
+        };
+        let split_roles = get_split_roles(&roles, &split);
+        let mut selected_roles = Vec::new();
+        for threshold in input.value.split(',') {
+            let threshold = threshold.trim();
+            if threshold.is_empty() {
+                continue;
+            }
+            let threshold = if threshold.eq_ignore_ascii_case("PB") {
+                "PB".to_string()
+            } else {
+                let (minutes, seconds) = parse_time_threshold(threshold)?;
+                format!("{}:{:02}", minutes, seconds)
+            };
+            match split_roles
+                .iter()
+                .find(|role| get_pace_role_threshold(role).as_ref() == Some(&threshold))
+            {
+                Some(role) => selected_roles.push(role.id),
+                None => {
+                    return Err(format!(
+                        "no pace-role for {} at '{}', expected any of: {}.",
+                        split.alt_desc(),
+                        threshold,
+                        join_thresholds(&split_roles)
+                    )
+                    .into())
+                }
+            }
+        }
+        for role in split_roles {
+            let has_role = member.roles.contains(&role.id);
+            let wants_role = selected_roles.contains(&role.id);
+            if has_role && !wants_role {
+                roles_to_remove.push(role.id);
+            } else if wants_role && !has_role {
+                roles_to_add.push(role.id);
+            }
+        }
+    }
 
+    if !roles_to_remove.is_empty() {
+        member.remove_roles(&ctx, &roles_to_remove).await?;
+    }
+    if !roles_to_add.is_empty() {
+        member.add_roles(&ctx, &roles_to_add).await?;
+    }
+    let locale = get_guild_locale(&cache, &guild_id).await;
+    modal_submit
+        .edit_original_interaction_response(&ctx.http, |r| r.content(locale.tr("Roles updated")))
+        .await?;
     Ok(())
 }
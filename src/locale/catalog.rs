@@ -0,0 +1,355 @@
+// (en, ja, pt-BR) translations, keyed by the english message.
+pub const CATALOG: &[(&str, &str, &str)] = &[
+    // Splits.
+    ("Enter Bastion", "砦の遺跡に突入", "Entrou no Bastião"),
+    ("Enter Fortress", "ネザー要塞に突入", "Entrou na Fortaleza do Nether"),
+    ("First Portal", "最初のポータル", "Primeiro Portal"),
+    ("Enter Stronghold", "要塞に突入", "Entrou na Fortaleza"),
+    ("Enter End", "エンドに突入", "Entrou no End"),
+    ("Finish", "完走", "Final"),
+    ("an unknown split", "不明なスプリット", "um split desconhecido"),
+    ("Structure 1", "ストラクチャー1", "Estrutura 1"),
+    ("Structure 2", "ストラクチャー2", "Estrutura 2"),
+    ("Blind", "ブラインド", "Blind"),
+    ("Eye Spy", "アイスパイ", "Eye Spy"),
+    ("End Enter", "エンド突入", "Entrada no End"),
+    // Pace messages.
+    ("Offline", "オフライン", "Offline"),
+    ("Splits", "スプリット", "Splits"),
+    ("Link", "リンク", "Link"),
+    ("Time", "時間", "Tempo"),
+    ("Items", "アイテム", "Itens"),
+    ("Bastionless", "砦の遺跡なし", "Sem Bastião"),
+    ("Yes", "はい", "Sim"),
+    ("Projected", "予測", "Projeção"),
+    ("Warning", "警告", "Aviso"),
+    ("{0}'s run ended at {1}.", "{0}のランは{1}で終了しました。", "A run de {0} terminou em {1}."),
+    ("From {0}", "{0}から", "De {0}"),
+    ("Pace during cooldown", "クールダウン中のペース", "Pace durante o cooldown"),
+    (
+        "Preview of a {0} pace message:",
+        "{0}のペースメッセージのプレビュー:",
+        "Pré-visualização de uma mensagem de pace de {0}:",
+    ),
+    // Role selection message.
+    (
+        "Select roles based on the splits and paces you wish to follow.",
+        "フォローしたいスプリットとペースのロールを選んでください。",
+        "Selecione os cargos dos splits e paces que você deseja acompanhar.",
+    ),
+    (
+        "Choose a First Structure Role...",
+        "ストラクチャー1のロールを選択...",
+        "Escolha um cargo de Estrutura 1...",
+    ),
+    (
+        "Choose a Second Structure Role...",
+        "ストラクチャー2のロールを選択...",
+        "Escolha um cargo de Estrutura 2...",
+    ),
+    ("Choose a Blind Role...", "ブラインドのロールを選択...", "Escolha um cargo de Blind..."),
+    (
+        "Choose an Eye Spy Role...",
+        "アイスパイのロールを選択...",
+        "Escolha um cargo de Eye Spy...",
+    ),
+    (
+        "Choose an End Enter Role...",
+        "エンド突入のロールを選択...",
+        "Escolha um cargo de Entrada no End...",
+    ),
+    ("Remove ALL PMB Roles", "PMBのロールをすべて削除", "Remover TODOS os cargos do PMB"),
+    ("PB Pace {0}", "PBペース {0}", "Pace de PB {0}"),
+    ("Sub {0} {1}", "{1} {0}未満", "{1} abaixo de {0}"),
+    ("Roles updated", "ロールを更新しました", "Cargos atualizados"),
+    (
+        "PaceManBot roles removed",
+        "PaceManBotのロールを削除しました",
+        "Cargos do PaceManBot removidos",
+    ),
+    ("My pings", "自分の通知", "Meus pings"),
+    ("Set all splits", "すべてのスプリットを設定", "Definir todos os splits"),
+    ("Set your pings", "通知を設定", "Defina seus pings"),
+    ("Your pace-roles:", "あなたのペースロール:", "Seus cargos de pace:"),
+    (
+        "You don't have any pace-roles yet.",
+        "まだペースロールがありません。",
+        "Você ainda não tem cargos de pace.",
+    ),
+    (
+        "No pace-roles are set up in this server.",
+        "このサーバーにはペースロールが設定されていません。",
+        "Nenhum cargo de pace foi configurado neste servidor.",
+    ),
+    (
+        "Times like 3:30 or PB, comma separated. Any of: {0}",
+        "3:30やPBなど、カンマ区切り。選択肢: {0}",
+        "Tempos como 3:30 ou PB, separados por vírgula. Opções: {0}",
+    ),
+    // Subscriptions.
+    (
+        "Updated subscriptions! You are not subscribed to any runners.",
+        "購読を更新しました!どのランナーも購読していません。",
+        "Inscrições atualizadas! Você não está inscrito em nenhum runner.",
+    ),
+    (
+        "Updated subscriptions! You are subscribed to:\n{0}",
+        "購読を更新しました!購読中:\n{0}",
+        "Inscrições atualizadas! Você está inscrito em:\n{0}",
+    ),
+    // Whitelist management.
+    (
+        "Updated config!",
+        "設定を更新しました!",
+        "Configuração atualizada!",
+    ),
+    (
+        "Updated config, but no minecraft account was found for uuid: '{0}'.",
+        "設定を更新しましたが、UUID: '{0}'のMinecraftアカウントが見つかりませんでした。",
+        "Configuração atualizada, mas nenhuma conta de minecraft foi encontrada para o uuid: '{0}'.",
+    ),
+    (
+        "Updated config, but failed to resolve the name for uuid: '{0}': {1}",
+        "設定を更新しましたが、UUID: '{0}'の名前を取得できませんでした: {1}",
+        "Configuração atualizada, mas não foi possível obter o nome do uuid: '{0}': {1}",
+    ),
+    (
+        "Updated config, but no minecraft account was found for: '{0}'.",
+        "設定を更新しましたが、'{0}'のMinecraftアカウントが見つかりませんでした。",
+        "Configuração atualizada, mas nenhuma conta de minecraft foi encontrada para: '{0}'.",
+    ),
+    (
+        "Updated config, but failed to resolve the uuid for: '{0}': {1}",
+        "設定を更新しましたが、'{0}'のUUIDを取得できませんでした: {1}",
+        "Configuração atualizada, mas não foi possível obter o uuid de: '{0}': {1}",
+    ),
+    (
+        "Whitelist",
+        "ホワイトリスト",
+        "Whitelist",
+    ),
+    (
+        "Whitelist matching '{0}'",
+        "'{0}'に一致するホワイトリスト",
+        "Whitelist com '{0}'",
+    ),
+    (
+        "No runners found.",
+        "ランナーが見つかりません。",
+        "Nenhum runner encontrado.",
+    ),
+    (
+        "Page {0}/{1} · {2} runners",
+        "ページ {0}/{1} · ランナー{2}人",
+        "Página {0}/{1} · {2} runners",
+    ),
+    (
+        "Previous",
+        "前へ",
+        "Anterior",
+    ),
+    (
+        "Next",
+        "次へ",
+        "Próxima",
+    ),
+    (
+        "Display name",
+        "表示名",
+        "Nome de exibição",
+    ),
+    (
+        "off",
+        "オフ",
+        "desligado",
+    ),
+    (
+        "sub {0}:00",
+        "{0}:00未満",
+        "abaixo de {0}:00",
+    ),
+    (
+        "Whitelist import preview ({0} unchanged):",
+        "ホワイトリストのインポートのプレビュー({0}件は変更なし):",
+        "Pré-visualização da importação da whitelist ({0} sem alteração):",
+    ),
+    (
+        "Nothing to import, all {0} runners are unchanged.",
+        "インポートするものはありません。{0}人のランナーはすべて変更なしです。",
+        "Nada para importar, todos os {0} runners estão iguais.",
+    ),
+    (
+        "Confirm",
+        "確定",
+        "Confirmar",
+    ),
+    (
+        "Cancel",
+        "キャンセル",
+        "Cancelar",
+    ),
+    (
+        "Import cancelled.",
+        "インポートをキャンセルしました。",
+        "Importação cancelada.",
+    ),
+    (
+        "Imported whitelist with {0} runners.",
+        "{0}人のランナーでホワイトリストをインポートしました。",
+        "Whitelist importada com {0} runners.",
+    ),
+    (
+        "Exported {0} runners.",
+        "{0}人のランナーをエクスポートしました。",
+        "{0} runners exportados.",
+    ),
+    (
+        "No minecraft account found for: {0}.",
+        "{0}のMinecraftアカウントが見つかりませんでした。",
+        "Nenhuma conta de minecraft encontrada para: {0}.",
+    ),
+    (
+        "Stored the uuids of {0} runners and updated the names of {1} runners.",
+        "{0}人のランナーのUUIDを保存し、{1}人のランナーの名前を更新しました。",
+        "Uuids de {0} runners salvos e nomes de {1} runners atualizados.",
+    ),
+    // Role selection messages.
+    (
+        "Sent message!",
+        "メッセージを送信しました!",
+        "Mensagem enviada!",
+    ),
+    (
+        "Sent message! Create #{0} to have it refreshed when the pace-roles change.",
+        "メッセージを送信しました!ペースロールが変わったときに更新するには#{0}を作成してください。",
+        "Mensagem enviada! Crie #{0} para que ela seja atualizada quando os cargos de pace mudarem.",
+    ),
+    // Configuration history.
+    (
+        "Configuration history",
+        "設定の履歴",
+        "Histórico de configuração",
+    ),
+    (
+        "No configuration changes were recorded yet.",
+        "まだ設定の変更は記録されていません。",
+        "Nenhuma alteração de configuração foi registrada ainda.",
+    ),
+    (
+        "Use /config_revert with an id to restore the configuration from before that change.",
+        "IDを指定して/config_revertを使うと、その変更前の設定を復元できます。",
+        "Use /config_revert com um id para restaurar a configuração de antes dessa alteração.",
+    ),
+    (
+        "Restored the whitelist to {0} lines.",
+        "ホワイトリストを{0}行に復元しました。",
+        "Whitelist restaurada para {0} linhas.",
+    ),
+    (
+        "Restored the settings to {0} lines.",
+        "設定を{0}行に復元しました。",
+        "Configurações restauradas para {0} linhas.",
+    ),
+    (
+        "Recreated {0} pace-roles: {1}. Members need to pick them again.",
+        "{0}個のペースロールを再作成しました: {1}。メンバーはもう一度選択する必要があります。",
+        "{0} cargos de pace recriados: {1}. Os membros precisam escolhê-los novamente.",
+    ),
+    (
+        "The configuration already matches how it was before change #{0}.",
+        "設定はすでに変更#{0}の前と同じです。",
+        "A configuração já está como era antes da alteração #{0}.",
+    ),
+    // Command names and descriptions.
+    ("subscribe", "購読", "inscrever"),
+    (
+        "Preview a pace message with the embed settings of this server.",
+        "このサーバーの埋め込み設定でペースメッセージをプレビューします。",
+        "Pré-visualiza uma mensagem de pace com as configurações de embed deste servidor.",
+    ),
+    (
+        "Remove all PB pace-roles.",
+        "PBペースロールをすべて削除します。",
+        "Remove todos os cargos de pace de PB.",
+    ),
+    (
+        "Remove all pace-roles based on split.",
+        "スプリットごとにペースロールをすべて削除します。",
+        "Remove todos os cargos de pace de um split.",
+    ),
+    (
+        "Send role message to the current channel.",
+        "このチャンネルにロール選択メッセージを送信します。",
+        "Envia a mensagem de cargos neste canal.",
+    ),
+    (
+        "Change or reset a bot setting for the server.",
+        "サーバーのボット設定を変更またはリセットします。",
+        "Altera ou redefine uma configuração do bot no servidor.",
+    ),
+    (
+        "Setup default pace-roles for sub 10.",
+        "サブ10用のデフォルトのペースロールを設定します。",
+        "Configura os cargos de pace padrão para sub 10.",
+    ),
+    (
+        "Setup split PB pace-roles(as specified per runner).",
+        "スプリットごとのPBペースロールを設定します(ランナーごとの指定に従います)。",
+        "Configura os cargos de pace de PB por split (conforme cada runner).",
+    ),
+    (
+        "Setup pings for specific runners.",
+        "特定のランナーの通知を設定します。",
+        "Configura pings para runners específicos.",
+    ),
+    (
+        "Setup pace-roles based on split, start time and end time in increments of 30s.",
+        "スプリット、開始時間と終了時間に基づいて30秒刻みでペースロールを設定します。",
+        "Configura cargos de pace por split, tempo inicial e final em intervalos de 30s.",
+    ),
+    (
+        "Get mentioned personally when a runner is on pace for a split, without pace-roles.",
+        "ペースロールなしで、ランナーがスプリットでペースに乗ったときに個人的にメンションを受け取ります。",
+        "Seja mencionado pessoalmente quando um runner estiver em pace num split, sem cargos de pace.",
+    ),
+    (
+        "Check if the current server configuration is valid and if the bot will work properly or not.",
+        "現在のサーバー設定が有効で、ボットが正しく動作するかを確認します。",
+        "Verifica se a configuração atual do servidor é válida e se o bot vai funcionar corretamente.",
+    ),
+    (
+        "Whitelist new players or edit old players' configurations in the server based on ign.",
+        "IGNでプレイヤーをホワイトリストに追加、または設定を編集します。",
+        "Adiciona jogadores à whitelist ou edita suas configurações pelo ign.",
+    ),
+    (
+        "Whitelist new players or edit old players' configurations in the server based on uuid.",
+        "UUIDでプレイヤーをホワイトリストに追加、または設定を編集します。",
+        "Adiciona jogadores à whitelist ou edita suas configurações pelo uuid.",
+    ),
+    (
+        "Import runners into the whitelist from a csv or json file.",
+        "CSVまたはJSONファイルからランナーをホワイトリストにインポートします。",
+        "Importa runners para a whitelist a partir de um arquivo csv ou json.",
+    ),
+    (
+        "Export the whitelist as a csv or json file.",
+        "ホワイトリストをCSVまたはJSONファイルとしてエクスポートします。",
+        "Exporta a whitelist como um arquivo csv ou json.",
+    ),
+    (
+        "Resolve and store the uuids of all whitelisted runners, so that name changes don't break them.",
+        "ホワイトリストの全ランナーのUUIDを取得して保存し、名前が変わっても動作するようにします。",
+        "Busca e salva os uuids de todos os runners da whitelist, para que mudanças de nome não os quebrem.",
+    ),
+    (
+        "List the recent configuration changes that can be reverted with /config_revert.",
+        "/config_revertで元に戻せる最近の設定変更を一覧表示します。",
+        "Lista as alterações recentes de configuração que podem ser desfeitas com /config_revert.",
+    ),
+    (
+        "Restore the whitelist, pace-roles and settings from before a change.",
+        "変更前のホワイトリスト、ペースロール、設定を復元します。",
+        "Restaura a whitelist, os cargos de pace e as configurações de antes de uma alteração.",
+    ),
+];
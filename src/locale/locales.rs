@@ -0,0 +1,57 @@
+use crate::locale::CATALOG;
+
+pub const LOCALES: [Locale; 3] = [Locale::En, Locale::Ja, Locale::PtBr];
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Locale {
+    En,
+    Ja,
+    PtBr,
+}
+
+impl Locale {
+    pub fn from_str(locale: &str) -> Option<Locale> {
+        match locale {
+            "en" => Some(Locale::En),
+            "ja" => Some(Locale::Ja),
+            "pt-BR" => Some(Locale::PtBr),
+            _ => None,
+        }
+    }
+
+    // Same as the locale codes used by Discord.
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ja => "ja",
+            Locale::PtBr => "pt-BR",
+        }
+    }
+
+    pub fn has_translation(&self, msgid: &str) -> bool {
+        *self != Locale::En && CATALOG.iter().any(|entry| entry.0 == msgid)
+    }
+
+    // Translates an english message, falling back to it if there is no translation.
+    pub fn tr(&self, msgid: &str) -> String {
+        let entry = match CATALOG.iter().find(|entry| entry.0 == msgid) {
+            Some(entry) => entry,
+            None => return msgid.to_string(),
+        };
+        match self {
+            Locale::En => entry.0,
+            Locale::Ja => entry.1,
+            Locale::PtBr => entry.2,
+        }
+        .to_string()
+    }
+
+    // Translates a message with `{0}`, `{1}`.. placeholders and fills them with `args`.
+    pub fn trf(&self, msgid: &str, args: &[&str]) -> String {
+        let mut message = self.tr(msgid);
+        for (idx, arg) in args.iter().enumerate() {
+            message = message.replace(format!("{{{}}}", idx).as_str(), arg);
+        }
+        message
+    }
+}
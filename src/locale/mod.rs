@@ -0,0 +1,5 @@
+mod catalog;
+mod locales;
+
+pub use catalog::CATALOG;
+pub use locales::{Locale, LOCALES};
@@ -5,7 +5,9 @@ mod dispatcher;
 mod env;
 mod handler;
 mod interaction;
+mod locale;
 mod log;
+mod resolver;
 mod ws;
 use serenity::client::Client;
 use serenity::framework::standard::StandardFramework;
@@ -19,6 +21,7 @@ use log::Log;
 
 use crate::cache::Cache;
 use crate::handler::Handler;
+use crate::resolver::{LocalUuidResolver, MojangUuidResolver, UuidResolver};
 use crate::ws::WS;
 
 #[tokio::main]
@@ -38,6 +41,18 @@ async fn main() -> Result<(), Box<dyn Error>> {
     );
     let framework = StandardFramework::new();
 
+    let resolver: Arc<dyn UuidResolver> = if env.uuid_resolver_file.is_empty() {
+        Arc::new(MojangUuidResolver::new())
+    } else {
+        match LocalUuidResolver::from_file(env.uuid_resolver_file.as_str()) {
+            Ok(resolver) => Arc::new(resolver),
+            Err(e) => {
+                eprintln!("Uuid resolver file parse error: {}", e);
+                return Err(e);
+            }
+        }
+    };
+
     let cache = Arc::new(Mutex::new(Cache::new()));
 
     let mut intents = GatewayIntents::all();
@@ -50,6 +65,9 @@ async fn main() -> Result<(), Box<dyn Error>> {
             log: Arc::new(log),
             cache,
             ws: Arc::new(ws),
+            resolver,
+            dev_guild_ids: env.dev_guild_ids.clone(),
+            remove_guild_commands: env.remove_guild_commands,
         })
         .framework(framework)
         .await?;
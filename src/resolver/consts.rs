@@ -0,0 +1,7 @@
+pub const MOJANG_PROFILE_URL: &str = "https://api.mojang.com/users/profiles/minecraft/";
+pub const MOJANG_BULK_PROFILE_URL: &str =
+    "https://api.minecraftservices.com/minecraft/profile/lookup/bulk/byname";
+pub const MOJANG_SESSION_PROFILE_URL: &str =
+    "https://sessionserver.mojang.com/session/minecraft/profile/";
+// The bulk endpoint accepts at most 10 names per request.
+pub const MOJANG_BULK_LIMIT: usize = 10;
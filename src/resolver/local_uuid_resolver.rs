@@ -0,0 +1,47 @@
+use std::{error::Error, fs};
+
+use serenity::async_trait;
+
+use crate::resolver::{hyphenate_uuid, ResolvedProfile, UuidResolver};
+
+// Resolves from a fixed list of profiles instead of mojang, for running the
+// bot locally and in tests without network access.
+pub struct LocalUuidResolver {
+    profiles: Vec<ResolvedProfile>,
+}
+
+impl LocalUuidResolver {
+    pub fn new(profiles: Vec<ResolvedProfile>) -> Self {
+        Self { profiles }
+    }
+
+    // Reads a json array of `{"id": "<uuid>", "name": "<name>"}` objects.
+    pub fn from_file(path: &str) -> Result<Self, Box<dyn Error>> {
+        let contents = fs::read_to_string(path)?;
+        let mut profiles = serde_json::from_str::<Vec<ResolvedProfile>>(contents.as_str())?;
+        for profile in profiles.iter_mut() {
+            profile.uuid = hyphenate_uuid(profile.uuid.as_str());
+        }
+        Ok(Self::new(profiles))
+    }
+}
+
+#[async_trait]
+impl UuidResolver for LocalUuidResolver {
+    async fn resolve_uuid(&self, name: &str) -> Result<Option<ResolvedProfile>, Box<dyn Error>> {
+        Ok(self
+            .profiles
+            .iter()
+            .find(|profile| profile.name.to_lowercase() == name.to_lowercase())
+            .cloned())
+    }
+
+    async fn resolve_name(&self, uuid: &str) -> Result<Option<ResolvedProfile>, Box<dyn Error>> {
+        let uuid = hyphenate_uuid(uuid);
+        Ok(self
+            .profiles
+            .iter()
+            .find(|profile| profile.uuid == uuid)
+            .cloned())
+    }
+}
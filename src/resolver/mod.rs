@@ -0,0 +1,9 @@
+mod consts;
+mod local_uuid_resolver;
+mod mojang_uuid_resolver;
+mod uuid_resolver;
+
+pub use consts::*;
+pub use local_uuid_resolver::LocalUuidResolver;
+pub use mojang_uuid_resolver::MojangUuidResolver;
+pub use uuid_resolver::{hyphenate_uuid, is_uuid, ResolvedProfile, UuidResolver};
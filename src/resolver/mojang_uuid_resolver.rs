@@ -0,0 +1,79 @@
+use std::{collections::HashMap, error::Error};
+
+use reqwest::{Client, StatusCode};
+use serenity::async_trait;
+
+use crate::resolver::{
+    hyphenate_uuid, ResolvedProfile, UuidResolver, MOJANG_BULK_LIMIT, MOJANG_BULK_PROFILE_URL,
+    MOJANG_PROFILE_URL, MOJANG_SESSION_PROFILE_URL,
+};
+
+pub struct MojangUuidResolver {
+    client: Client,
+}
+
+impl MojangUuidResolver {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+
+    async fn get_profile(&self, url: String) -> Result<Option<ResolvedProfile>, Box<dyn Error>> {
+        let response = self.client.get(url.as_str()).send().await?;
+        match response.status() {
+            StatusCode::OK => {
+                let mut profile = response.json::<ResolvedProfile>().await?;
+                profile.uuid = hyphenate_uuid(profile.uuid.as_str());
+                Ok(Some(profile))
+            }
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(None),
+            status => Err(format!("failed to get profile from: '{}': {}.", url, status).into()),
+        }
+    }
+}
+
+#[async_trait]
+impl UuidResolver for MojangUuidResolver {
+    async fn resolve_uuid(&self, name: &str) -> Result<Option<ResolvedProfile>, Box<dyn Error>> {
+        self.get_profile(format!("{}{}", MOJANG_PROFILE_URL, name))
+            .await
+    }
+
+    async fn resolve_name(&self, uuid: &str) -> Result<Option<ResolvedProfile>, Box<dyn Error>> {
+        self.get_profile(format!(
+            "{}{}",
+            MOJANG_SESSION_PROFILE_URL,
+            uuid.replace('-', "")
+        ))
+        .await
+    }
+
+    async fn resolve_uuids(
+        &self,
+        names: &[String],
+    ) -> Result<HashMap<String, ResolvedProfile>, Box<dyn Error>> {
+        let mut profiles = HashMap::new();
+        for chunk in names.chunks(MOJANG_BULK_LIMIT) {
+            let response = self
+                .client
+                .post(MOJANG_BULK_PROFILE_URL)
+                .json(chunk)
+                .send()
+                .await?;
+            if response.status() != StatusCode::OK {
+                return Err(format!(
+                    "failed to get profiles from: '{}': {}.",
+                    MOJANG_BULK_PROFILE_URL,
+                    response.status()
+                )
+                .into());
+            }
+            for mut profile in response.json::<Vec<ResolvedProfile>>().await? {
+                profile.uuid = hyphenate_uuid(profile.uuid.as_str());
+                profiles.insert(profile.name.to_lowercase(), profile);
+            }
+        }
+        Ok(profiles)
+    }
+}
@@ -0,0 +1,54 @@
+use std::{collections::HashMap, error::Error};
+
+use serde::Deserialize;
+use serenity::async_trait;
+
+#[derive(Deserialize, Clone)]
+pub struct ResolvedProfile {
+    #[serde(rename = "id")]
+    pub uuid: String,
+    pub name: String,
+}
+
+// Resolves between in-game names and uuids, so that whitelist entries survive
+// name changes. `Ok(None)` means that the account doesn't exist.
+#[async_trait]
+pub trait UuidResolver: Send + Sync {
+    async fn resolve_uuid(&self, name: &str) -> Result<Option<ResolvedProfile>, Box<dyn Error>>;
+    async fn resolve_name(&self, uuid: &str) -> Result<Option<ResolvedProfile>, Box<dyn Error>>;
+
+    // Keyed by lowercase name, names that don't exist are left out.
+    async fn resolve_uuids(
+        &self,
+        names: &[String],
+    ) -> Result<HashMap<String, ResolvedProfile>, Box<dyn Error>> {
+        let mut profiles = HashMap::new();
+        for name in names {
+            if let Some(profile) = self.resolve_uuid(name).await? {
+                profiles.insert(name.to_lowercase(), profile);
+            }
+        }
+        Ok(profiles)
+    }
+}
+
+pub fn is_uuid(text: &str) -> bool {
+    let hex = text.replace('-', "");
+    hex.len() == 32 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+// Paceman and the whitelist use hyphenated uuids, mojang doesn't.
+pub fn hyphenate_uuid(uuid: &str) -> String {
+    let uuid = uuid.replace('-', "").to_lowercase();
+    if uuid.len() != 32 {
+        return uuid;
+    }
+    format!(
+        "{}-{}-{}-{}-{}",
+        &uuid[0..8],
+        &uuid[8..12],
+        &uuid[12..16],
+        &uuid[16..20],
+        &uuid[20..32]
+    )
+}
@@ -1,6 +1,6 @@
 use serde::Deserialize;
 
-#[derive(Deserialize, Debug, PartialEq, Clone, Copy)]
+#[derive(Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum EventId {
     #[serde(rename = "common.open_to_lan")]
     CommonOpenToLan,
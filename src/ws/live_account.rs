@@ -0,0 +1,170 @@
+use serde::Deserialize;
+
+use crate::dispatcher::{
+    KICK_EMOJI, KICK_LINK_PREFIX, TWITCH_EMOJI, TWITCH_LINK_PREFIX, YOUTUBE_EMOJI,
+    YOUTUBE_LINK_PREFIX,
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Platform {
+    Twitch,
+    YouTube,
+    Kick,
+}
+
+pub const PLATFORMS: [Platform; 3] = [Platform::Twitch, Platform::YouTube, Platform::Kick];
+
+impl Platform {
+    pub fn from_str(platform: &str) -> Option<Platform> {
+        match platform {
+            "twitch" => Some(Platform::Twitch),
+            "youtube" => Some(Platform::YouTube),
+            "kick" => Some(Platform::Kick),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Platform::Twitch => "twitch",
+            Platform::YouTube => "youtube",
+            Platform::Kick => "kick",
+        }
+    }
+
+    pub fn link_prefix(self) -> &'static str {
+        match self {
+            Platform::Twitch => TWITCH_LINK_PREFIX,
+            Platform::YouTube => YOUTUBE_LINK_PREFIX,
+            Platform::Kick => KICK_LINK_PREFIX,
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Platform::Twitch => TWITCH_EMOJI,
+            Platform::YouTube => YOUTUBE_EMOJI,
+            Platform::Kick => KICK_EMOJI,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LiveAccount {
+    pub platform: Platform,
+    pub name: String,
+}
+
+impl LiveAccount {
+    pub fn link(&self) -> String {
+        format!("{}{}", self.platform.link_prefix(), self.name)
+    }
+}
+
+// paceman sends either a plain twitch name, or an object with `main`/`alt`
+// twitch names and optionally names for other platforms.
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+pub enum LiveAccountField {
+    Name(String),
+    Accounts {
+        main: Option<String>,
+        alt: Option<String>,
+        twitch: Option<String>,
+        youtube: Option<String>,
+        kick: Option<String>,
+    },
+}
+
+impl LiveAccountField {
+    pub fn accounts(&self) -> Vec<LiveAccount> {
+        let names = match self {
+            LiveAccountField::Name(name) => vec![(Platform::Twitch, Some(name))],
+            LiveAccountField::Accounts {
+                main,
+                alt,
+                twitch,
+                youtube,
+                kick,
+            } => vec![
+                (Platform::Twitch, main.as_ref()),
+                (Platform::Twitch, alt.as_ref()),
+                (Platform::Twitch, twitch.as_ref()),
+                (Platform::YouTube, youtube.as_ref()),
+                (Platform::Kick, kick.as_ref()),
+            ],
+        };
+        names
+            .into_iter()
+            .filter_map(|(platform, name)| {
+                let name = name?.trim();
+                // paceman fills in missing accounts with `null` or `_null_`.
+                if name.is_empty()
+                    || name.eq_ignore_ascii_case("null")
+                    || name.eq_ignore_ascii_case("_null_")
+                {
+                    return None;
+                }
+                Some(LiveAccount {
+                    platform,
+                    name: name.to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    fn live_account_field(fixture: &str) -> LiveAccountField {
+        let response: Value = serde_json::from_str(fixture).unwrap();
+        serde_json::from_value(response["user"]["liveAccount"].to_owned()).unwrap()
+    }
+
+    #[test]
+    fn null_sentinels_are_not_live_accounts() {
+        for fixture in [
+            include_str!("../../tests/json/6-end-event.json"),
+            include_str!("../../tests/json/3-aa-hdwgh-event.json"),
+        ] {
+            assert!(live_account_field(fixture).accounts().is_empty());
+        }
+    }
+
+    #[test]
+    fn plain_name_is_a_twitch_account() {
+        let field: LiveAccountField = serde_json::from_str("\"specnr\"").unwrap();
+        assert_eq!(
+            field.accounts(),
+            vec![LiveAccount {
+                platform: Platform::Twitch,
+                name: "specnr".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn accounts_on_all_platforms() {
+        let field: LiveAccountField = serde_json::from_str(
+            r#"{"main": "NULL", "alt": " ", "twitch": "a", "youtube": "b", "kick": "c"}"#,
+        )
+        .unwrap();
+        let platforms = field
+            .accounts()
+            .iter()
+            .map(|account| (account.platform, account.name.to_owned()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            platforms,
+            vec![
+                (Platform::Twitch, "a".to_string()),
+                (Platform::YouTube, "b".to_string()),
+                (Platform::Kick, "c".to_string()),
+            ]
+        );
+    }
+}
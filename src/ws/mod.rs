@@ -1,11 +1,13 @@
 mod consts;
 mod event_id;
 mod item;
+mod live_account;
 mod ws;
 mod ws_response;
 
 pub use consts::*;
 pub use event_id::EventId;
 pub use item::Item;
+pub use live_account::{LiveAccount, LiveAccountField, Platform, PLATFORMS};
 pub use ws::WS;
 pub use ws_response::*;
@@ -5,7 +5,7 @@ use serde::Deserialize;
 use crate::{
     cache::{Split, Structure},
     dispatcher::{RunInfo, PEARL_EMOJI, ROD_EMOJI},
-    ws::{EventId, Item},
+    ws::{EventId, Item, LiveAccount, LiveAccountField, Platform, PLATFORMS},
 };
 
 #[derive(Deserialize, PartialEq, Clone, Copy)]
@@ -20,7 +20,23 @@ pub struct Event {
 #[serde(rename_all = "camelCase")]
 pub struct User {
     pub uuid: String,
-    pub live_account: Option<String>,
+    pub live_account: Option<LiveAccountField>,
+}
+
+impl User {
+    // First live account on one of `platforms`, in the order of `PLATFORMS`.
+    pub fn live_account_on(&self, platforms: &[Platform]) -> Option<LiveAccount> {
+        let accounts = self.live_account.as_ref()?.accounts();
+        PLATFORMS
+            .iter()
+            .filter(|platform| platforms.contains(platform))
+            .find_map(|platform| {
+                accounts
+                    .iter()
+                    .find(|account| &account.platform == platform)
+                    .cloned()
+            })
+    }
 }
 
 #[derive(Deserialize, Clone)]
@@ -76,9 +92,36 @@ pub struct WSResponse {
     pub event_list: Vec<Event>,
     pub context_event_list: Vec<Event>,
     pub user: User,
-    pub _is_cheated: bool,
-    pub _is_hidden: bool,
+    pub is_cheated: bool,
+    pub is_hidden: bool,
     pub last_updated: i64,
     pub item_data: Option<ItemData>,
     pub nickname: String,
 }
+
+impl WSResponse {
+    pub fn cheat_flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        if self.is_cheated {
+            flags.push("Cheated".to_string());
+        }
+        if self.is_hidden {
+            flags.push("Hidden".to_string());
+        }
+        for (event_id, flag) in [
+            (EventId::CommonEnableCheats, "Cheats enabled"),
+            (EventId::CommonViewSeed, "Seed viewed"),
+            (EventId::CommonOpenToLan, "Opened to LAN"),
+        ] {
+            if self
+                .event_list
+                .iter()
+                .chain(self.context_event_list.iter())
+                .any(|evt| evt.event_id == event_id)
+            {
+                flags.push(flag.to_string());
+            }
+        }
+        flags
+    }
+}